@@ -0,0 +1,113 @@
+pub use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use how_integrity::{Section, LinkTypes};
+
+use crate::document::*;
+use crate::error::*;
+use crate::tree::{get_entry_hashes, tree_path, tree_path_to_str, UnitInfo};
+use crate::unit::get_unit_entry;
+
+/// one contribution to a collection, a section of a descendant unit's current document
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionItem {
+    pub path: String,
+    pub unit_hash: EntryHash,
+    pub document_hash: EntryHashB64,
+    pub title: String,
+    pub section: Section,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionDefinition {
+    pub name: String,
+    pub content_type: String,
+    pub description: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Collection {
+    pub unit_hash: EntryHash,
+    pub path: String,
+    pub document_hash: EntryHashB64,
+    pub definitions: Vec<CollectionDefinition>,
+    pub items: Vec<CollectionItem>,
+    pub conflicts: Vec<String>, // paths of descendants left out because their document has conflicting heads
+}
+
+// all the units below path, depth first with siblings in path order
+fn get_descendants(path: Path, descendants: &mut Vec<(String, UnitInfo)>) -> ExternResult<()> {
+    let mut children = vec![];
+    for child in path.into_typed(ScopedLinkType::try_from(LinkTypes::Tree)?).children_paths()? {
+        let (units, _) = get_entry_hashes(&child)?;
+        if !units.is_empty() {
+            children.push((tree_path_to_str(child.path.clone())?, units, child.path));
+        }
+    }
+    children.sort_by(|a, b| a.0.cmp(&b.0));
+    for (path_str, units, child) in children {
+        for unit in units {
+            descendants.push((path_str.clone(), unit));
+        }
+        get_descendants(child, descendants)?;
+    }
+    Ok(())
+}
+
+/// gathers the contributions to a unit's collection.  The definitions are the `d` sections
+/// of the unit's current document, the contributions are all the sections of the current
+/// documents of the units below it that were sourced from the unit's path, whatever
+/// their name.
+#[hdk_extern]
+pub fn get_collection(unit_hash: EntryHash) -> ExternResult<Collection> {
+    let unit = get_unit_entry(unit_hash.clone())?;
+    let path = unit.path_str()?;
+    let doc = get_current_head(unit_hash.clone())?;
+    let definitions: Vec<CollectionDefinition> = doc.content.content.iter()
+        .filter(|s| s.section_type == SECTION_TYPE_COLLECTION_DEF)
+        .map(|s| CollectionDefinition {
+            name: s.name.clone(),
+            content_type: s.content_type.clone(),
+            description: s.content.clone(),
+        })
+        .collect();
+
+    let mut descendants = vec![];
+    get_descendants(tree_path(path.clone()), &mut descendants)?;
+    let mut items = vec![];
+    let mut conflicts = vec![];
+    for (descendant_path, info) in descendants {
+        let head = match _get_current_document(info.hash.clone()) {
+            Ok(CurrentDocument::Head(head)) => head,
+            Ok(CurrentDocument::Conflict(_)) => {
+                conflicts.push(descendant_path);
+                continue;
+            }
+            Err(HowError::DocumentNotFound) => continue,
+            Err(err) => return Err(err.into()),
+        };
+        let title = head.content.content.iter()
+            .find(|s| s.name == "title")
+            .map(|s| s.content.clone())
+            .unwrap_or_default();
+        for section in head.content.content.iter().filter(|s| s.source_path == path) {
+            items.push(CollectionItem {
+                path: descendant_path.clone(),
+                unit_hash: info.hash.clone(),
+                document_hash: head.hash.clone(),
+                title: title.clone(),
+                section: section.clone(),
+            });
+        }
+    }
+    Ok(Collection {
+        unit_hash,
+        path,
+        document_hash: doc.hash,
+        definitions,
+        items,
+        conflicts,
+    })
+}
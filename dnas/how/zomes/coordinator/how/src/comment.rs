@@ -0,0 +1,676 @@
+use std::collections::{BTreeMap, HashMap};
+
+pub use hdk::prelude::*;
+use hdk::prelude::holo_hash::AgentPubKeyB64;
+use holo_hash::EntryHashB64;
+use how_integrity::{Document, DocumentMeta, CommentMeta, Section, Unit, LinkTypes};
+
+use crate::document::*;
+use crate::error::*;
+use crate::merge::{document_path, document_record};
+
+pub const SECTION_COMMENT: &str = "comment";
+pub const SECTION_SUGGESTION: &str = "suggestion";
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Modified,
+}
+
+impl CommentStatus {
+    pub fn from_mark(mark: &str) -> Option<Self> {
+        match mark {
+            "pending" => Some(CommentStatus::Pending),
+            "approved" => Some(CommentStatus::Approved),
+            "rejected" => Some(CommentStatus::Rejected),
+            "modified" => Some(CommentStatus::Modified),
+            _ => None,
+        }
+    }
+}
+
+/// A comment on a range of a section of a document.  Comments are stored as documents
+/// of type DOC_COMMENT, with the anchor in the meta data and the text and suggestion as
+/// sections.  Offsets count UTF-16 code units, as they come from the browser.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    pub document: EntryHashB64,
+    pub section: String,
+    pub start_offset: usize,
+    pub end_offset: usize,
+    pub text: String,
+    pub suggestion: Option<String>,
+    #[serde(default)]
+    pub anchor: Option<CommentAnchor>,
+    #[serde(default)]
+    pub orphaned: bool, // the quoted text could not be found in this revision
+}
+
+// how many UTF-16 code units of context to keep on either side of the quoted text
+pub const ANCHOR_CONTEXT: usize = 32;
+
+/// The commented text and some context around it, so a comment can be found again
+/// after its section has been edited and the offsets no longer point at the right text.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentAnchor {
+    pub quote: String,
+    pub prefix: String,
+    pub suffix: String,
+}
+
+fn utf16_slice(units: &[u16], start: usize, end: usize) -> String {
+    String::from_utf16_lossy(&units[start.min(units.len())..end.min(units.len())])
+}
+
+impl CommentAnchor {
+    pub fn from_text(text: &str, start: usize, end: usize) -> Option<Self> {
+        let units: Vec<u16> = text.encode_utf16().collect();
+        if start > end || end > units.len() {
+            return None;
+        }
+        Some(CommentAnchor {
+            quote: utf16_slice(&units, start, end),
+            prefix: utf16_slice(&units, start.saturating_sub(ANCHOR_CONTEXT), start),
+            suffix: utf16_slice(&units, end, end + ANCHOR_CONTEXT),
+        })
+    }
+
+    // finds the quote in text, preferring the occurrence whose surroundings best match the
+    // stored context and then the one closest to where the comment used to be
+    pub fn locate(&self, text: &str, near: usize) -> Option<(usize, usize)> {
+        let units: Vec<u16> = text.encode_utf16().collect();
+        let quote: Vec<u16> = self.quote.encode_utf16().collect();
+        let prefix: Vec<u16> = self.prefix.encode_utf16().collect();
+        let suffix: Vec<u16> = self.suffix.encode_utf16().collect();
+        if quote.is_empty() {
+            return locate_point(&units, &prefix, &suffix, near);
+        }
+        if quote.len() > units.len() {
+            return None;
+        }
+        let mut best: Option<(usize, usize, usize)> = None; // (start, score, distance)
+        for start in 0..=(units.len() - quote.len()) {
+            if units[start..start + quote.len()] != quote[..] {
+                continue;
+            }
+            let end = start + quote.len();
+            let (before, after) = context_match(&units, start, end, &prefix, &suffix);
+            best = closer(best, start, before + after, near);
+        }
+        best.map(|(start, _, _)| (start, start + quote.len()))
+    }
+}
+
+// how many units of the prefix match right before start and of the suffix right after end
+fn context_match(units: &[u16], start: usize, end: usize, prefix: &[u16], suffix: &[u16]) -> (usize, usize) {
+    let before = prefix.iter().rev().zip(units[..start].iter().rev()).take_while(|(a, b)| a == b).count();
+    let after = suffix.iter().zip(units[end..].iter()).take_while(|(a, b)| a == b).count();
+    (before, after)
+}
+
+// keeps the better of the best match so far and a match at start: the higher score, or
+// on equal scores the one closer to near
+fn closer(best: Option<(usize, usize, usize)>, start: usize, score: usize, near: usize) -> Option<(usize, usize, usize)> {
+    let distance = if start > near { start - near } else { near - start };
+    match best {
+        Some((_, best_score, best_distance)) if score < best_score || (score == best_score && distance >= best_distance) => best,
+        _ => Some((start, score, distance)),
+    }
+}
+
+// An insertion point has no text of its own so it can only be found by its context.
+// It stays put as long as the text on one side of it is intact.
+fn locate_point(units: &[u16], prefix: &[u16], suffix: &[u16], near: usize) -> Option<(usize, usize)> {
+    if prefix.is_empty() && suffix.is_empty() {
+        let at = near.min(units.len());
+        return Some((at, at));
+    }
+    let mut best: Option<(usize, usize, usize)> = None;
+    for at in 0..=units.len() {
+        let (before, after) = context_match(units, at, at, prefix, suffix);
+        let intact = (!prefix.is_empty() && before == prefix.len()) || (!suffix.is_empty() && after == suffix.len());
+        if intact {
+            best = closer(best, at, before + after, near);
+        }
+    }
+    best.map(|(at, _, _)| (at, at))
+}
+
+impl Comment {
+    pub fn from_document(document: &Document) -> Option<Self> {
+        if document.document_type != DOC_COMMENT {
+            return None;
+        }
+        let section_content = |name: &str| document.content.iter().find(|s| s.name == name).map(|s| s.content.clone());
+        let meta = document.meta.comment.as_ref()?;
+        let anchor = match &meta.quote {
+            Some(quote) => Some(CommentAnchor {
+                quote: quote.clone(),
+                prefix: meta.prefix.clone().unwrap_or_default(),
+                suffix: meta.suffix.clone().unwrap_or_default(),
+            }),
+            None => None,
+        };
+        Some(Comment {
+            document: EntryHashB64::from_b64_str(&meta.document).ok()?,
+            section: meta.section.clone(),
+            start_offset: meta.start_offset,
+            end_offset: meta.end_offset,
+            text: section_content(SECTION_COMMENT).unwrap_or_default(),
+            suggestion: section_content(SECTION_SUGGESTION),
+            anchor,
+            orphaned: false,
+        })
+    }
+
+    pub fn to_document(&self, commenting_on: &Document, author: AgentPubKeyB64) -> Document {
+        let mut content = vec![];
+        if self.text != "" {
+            content.push(Section::new(SECTION_COMMENT, SECTION_TYPE_CONTENT, "text/markdown", SOURCE_MANUAL, None, &self.text));
+        }
+        if let Some(suggestion) = &self.suggestion {
+            content.push(Section::new(SECTION_SUGGESTION, SECTION_TYPE_CONTENT, "text/markdown", SOURCE_MANUAL, None, suggestion));
+        }
+        let mut meta = DocumentMeta::new();
+        meta.comment = Some(CommentMeta {
+            document: self.document.to_string(),
+            section: self.section.clone(),
+            start_offset: self.start_offset,
+            end_offset: self.end_offset,
+            quote: self.anchor.as_ref().map(|a| a.quote.clone()),
+            prefix: self.anchor.as_ref().map(|a| a.prefix.clone()),
+            suffix: self.anchor.as_ref().map(|a| a.suffix.clone()),
+        });
+        Document {
+            unit_hash: commenting_on.unit_hash.clone(),
+            document_type: String::from(DOC_COMMENT),
+            state: commenting_on.state.clone(),
+            editors: vec![author],
+            content,
+            meta,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentOutput {
+    pub hash: EntryHashB64,
+    pub comment: Comment,
+    pub status: CommentStatus,
+    pub author: AgentPubKeyB64,
+    pub timestamp: Timestamp,
+    pub marks: Vec<Mark>,
+}
+
+/// Where a comment sits in a revision other than the one it was made on, stored in the
+/// tag of the Comment link from that revision.
+#[derive(Clone, Serialize, Deserialize, Debug, SerializedBytes)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentPlacement {
+    pub start_offset: usize,
+    pub end_offset: usize,
+    pub orphaned: bool,
+}
+
+impl CommentPlacement {
+    pub fn from_tag(tag: LinkTag) -> Option<Self> {
+        let bytes = tag.into_inner();
+        if bytes.is_empty() {
+            return None;
+        }
+        CommentPlacement::try_from(SerializedBytes::from(UnsafeBytes::from(bytes))).ok()
+    }
+
+    pub fn to_tag(&self) -> HowResult<LinkTag> {
+        let bytes = SerializedBytes::try_from(self.clone())?;
+        Ok(LinkTag::new(bytes.bytes().clone()))
+    }
+}
+
+impl CommentOutput {
+    pub fn place(&mut self, document: EntryHashB64, placement: CommentPlacement) {
+        self.comment.document = document;
+        self.comment.start_offset = placement.start_offset;
+        self.comment.end_offset = placement.end_offset;
+        self.comment.orphaned = placement.orphaned;
+    }
+
+    pub fn from_output(output: DocumentOutput) -> Option<Self> {
+        let comment = Comment::from_document(&output.content)?;
+        // the most recent status mark wins, a comment without one is pending
+        let status = output.marks.iter()
+            .filter(|m| m.mark_type == MARK_TYPE_COMMENT_STATUS)
+            .filter_map(|m| CommentStatus::from_mark(&m.mark))
+            .last()
+            .unwrap_or(CommentStatus::Pending);
+        let action = output.actions.first()?.as_content();
+        Some(CommentOutput {
+            hash: output.hash,
+            comment,
+            status,
+            author: action.author().clone().into(),
+            timestamp: action.timestamp(),
+            marks: output.marks,
+        })
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCommentInput {
+    pub document: EntryHash,
+    pub section: String,
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    pub suggestion: Option<String>,
+}
+
+/// comments on a range of a section of a document, the comment gets linked from the
+/// document so it can be found without scanning the documents at the unit's path
+#[hdk_extern]
+pub fn create_comment(input: CreateCommentInput) -> ExternResult<EntryHashB64> {
+    let commenting_on = get_document_entry(input.document.clone())?;
+    let anchor = commenting_on.content.iter()
+        .find(|s| s.name == input.section)
+        .and_then(|s| CommentAnchor::from_text(&s.content, input.start, input.end));
+    let comment = Comment {
+        document: input.document.clone().into(),
+        section: input.section,
+        start_offset: input.start,
+        end_offset: input.end,
+        text: input.text,
+        suggestion: input.suggestion,
+        anchor,
+        orphaned: false,
+    };
+    let document = comment.to_document(&commenting_on, agent_info()?.agent_initial_pubkey.into());
+    let hash = create_document(DocumentInput {
+        path: document_path(&commenting_on)?,
+        document,
+    })?;
+    // a comment on a comment is a reply, and is linked as one so that threads can be walked
+    let link_type = if commenting_on.document_type == DOC_COMMENT { LinkTypes::Reply } else { LinkTypes::Comment };
+    create_link(input.document, EntryHash::from(hash.clone()), link_type, ())?;
+    Ok(hash)
+}
+
+// comments linked from base, placed where the link says they sit in base
+fn get_linked_comments(base: EntryHash, link_type: LinkTypes) -> HowResult<Vec<CommentOutput>> {
+    let mut links = get_links(base.clone(), link_type, None)?;
+    links.sort_by_key(|l| l.timestamp);
+    let mut placements: HashMap<AnyLinkableHash, CommentPlacement> = HashMap::new();
+    let mut get_input = vec![];
+    for link in links {
+        if let Some(placement) = CommentPlacement::from_tag(link.tag.clone()) {
+            placements.insert(link.target.clone(), placement);
+        }
+        if let Ok(hash) = AnyDhtHash::try_from(link.target) {
+            get_input.push(GetInput::new(hash, GetOptions::default()))
+        }
+    }
+    let base: EntryHashB64 = base.into();
+    let mut comments = vec![];
+    for doc in _get_docs(get_input)? {
+        if !doc.deleted_by.is_empty() {
+            continue;
+        }
+        let target: AnyLinkableHash = EntryHash::from(doc.hash.clone()).into();
+        let placement = placements.remove(&target);
+        if let Some(mut comment) = CommentOutput::from_output(doc) {
+            if let Some(placement) = placement {
+                comment.place(base.clone(), placement);
+            }
+            comments.push(comment);
+        }
+    }
+    Ok(comments)
+}
+
+pub fn _get_comments(document_hash: EntryHash) -> HowResult<Vec<CommentOutput>> {
+    let mut comments = get_linked_comments(document_hash.clone(), LinkTypes::Comment)?;
+    // comments made before there were comment links can only be found at the path, and
+    // a document can have both kinds
+    let document = get_document_entry(document_hash.clone())?;
+    let path = crate::tree::tree_path(document_path(&document)?);
+    for comment in get_documents_inner(path.path_entry_hash()?)?
+        .into_iter()
+        .filter(|d| d.deleted_by.is_empty())
+        .filter_map(CommentOutput::from_output)
+    {
+        if !comments.iter().any(|c| c.hash == comment.hash) {
+            comments.push(comment);
+        }
+    }
+    let document_hash: EntryHashB64 = document_hash.into();
+    Ok(comments.into_iter()
+        .filter(|c| c.comment.document == document_hash)
+        .collect())
+}
+
+#[hdk_extern]
+pub fn get_comments(document_hash: EntryHash) -> ExternResult<Vec<CommentOutput>> {
+    Ok(_get_comments(document_hash)?)
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentThread {
+    pub comment: CommentOutput,
+    pub replies: Vec<CommentThread>,
+}
+
+impl CommentThread {
+    pub fn reply_count(&self) -> u32 {
+        self.replies.iter().map(|r| 1 + r.reply_count()).sum()
+    }
+}
+
+fn build_thread(comment: CommentOutput) -> HowResult<CommentThread> {
+    let mut replies = vec![];
+    let mut reply_outputs = get_linked_comments(EntryHash::from(comment.hash.clone()), LinkTypes::Reply)?;
+    reply_outputs.sort_by_key(|r| r.timestamp);
+    for reply in reply_outputs {
+        replies.push(build_thread(reply)?);
+    }
+    Ok(CommentThread {
+        comment,
+        replies,
+    })
+}
+
+/// returns a comment with all of its replies nested below it, oldest reply first
+#[hdk_extern]
+pub fn get_comment_thread(comment_hash: EntryHash) -> ExternResult<CommentThread> {
+    let output = get_document(comment_hash)?;
+    let comment = CommentOutput::from_output(output)
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Document is not a comment"))))?;
+    Ok(build_thread(comment)?)
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionThreads {
+    pub threads: u32,
+    pub replies: u32,
+    pub pending: u32,
+    pub approved: u32,
+    pub rejected: u32,
+    pub modified: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadSummary {
+    pub threads: u32,
+    pub sections: BTreeMap<String, SectionThreads>,
+}
+
+/// counts the comment threads on each section of a document, statuses are those of the
+/// comment that started the thread
+#[hdk_extern]
+pub fn get_thread_summary(document_hash: EntryHash) -> ExternResult<ThreadSummary> {
+    let mut summary = ThreadSummary::default();
+    for comment in _get_comments(document_hash)? {
+        let section = comment.comment.section.clone();
+        let status = comment.status.clone();
+        let thread = build_thread(comment)?;
+        let counts = summary.sections.entry(section).or_default();
+        counts.threads += 1;
+        counts.replies += thread.reply_count();
+        match status {
+            CommentStatus::Pending => counts.pending += 1,
+            CommentStatus::Approved => counts.approved += 1,
+            CommentStatus::Rejected => counts.rejected += 1,
+            CommentStatus::Modified => counts.modified += 1,
+        }
+        summary.threads += 1;
+    }
+    Ok(summary)
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedComment {
+    pub hash: EntryHashB64,
+    pub reason: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyCommentsOutput {
+    pub hash: Option<EntryHashB64>, // the new revision, None if there was nothing to apply
+    pub applied: Vec<EntryHashB64>,
+    pub skipped: Vec<SkippedComment>,
+}
+
+// only status marks made by one of the stewards count towards a comment's approval,
+// and of those the most recent one.  Marks are judged by who made their link, as the
+// provenance of an imported mark is a claim anyone could have linked.
+pub fn steward_status(comment: &CommentOutput, stewards: &[AgentPubKeyB64]) -> HowResult<Option<CommentStatus>> {
+    Ok(get_mark_details(EntryHash::from(comment.hash.clone()))?
+        .into_iter()
+        .filter(|m| m.mark_type == MARK_TYPE_COMMENT_STATUS && stewards.contains(&m.author))
+        .filter_map(|m| CommentStatus::from_mark(&m.mark).map(|status| (m.timestamp, status)))
+        .max_by_key(|(timestamp, _)| *timestamp)
+        .map(|(_, status)| status))
+}
+
+// replaces a range of text given in UTF-16 code units, which is how the UI counts offsets
+pub fn splice_utf16(text: &str, start: usize, end: usize, replacement: &str) -> Option<String> {
+    let units: Vec<u16> = text.encode_utf16().collect();
+    if start > end || end > units.len() {
+        return None;
+    }
+    let mut spliced: Vec<u16> = units[..start].to_vec();
+    spliced.extend(replacement.encode_utf16());
+    spliced.extend_from_slice(&units[end..]);
+    String::from_utf16(&spliced).ok()
+}
+
+/// applies the suggestions of all steward approved comments on a document and commits
+/// the result as a new revision that records which comments went into it
+#[hdk_extern]
+pub fn apply_approved_comments(document_hash: EntryHash) -> ExternResult<ApplyCommentsOutput> {
+    let (record, mut document) = document_record(document_hash.clone())?;
+    let unit_record = get(document.unit_hash.clone(), GetOptions::default())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Unit not found"))))?;
+    let unit: Unit = unit_record
+        .entry()
+        .to_app_option().map_err(|err| wasm_error!(err))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Malformed unit"))))?;
+
+    let mut skipped = vec![];
+    let mut approved = vec![];
+    for comment in _get_comments(document_hash.clone())? {
+        if comment.comment.suggestion.is_none() {
+            continue;
+        }
+        if comment.comment.orphaned {
+            skipped.push(SkippedComment { hash: comment.hash, reason: String::from("commented text no longer exists") });
+            continue;
+        }
+        match steward_status(&comment, &unit.stewards)? {
+            Some(CommentStatus::Approved) => approved.push(comment),
+            _ => {
+                if comment.status == CommentStatus::Approved {
+                    skipped.push(SkippedComment { hash: comment.hash, reason: String::from("not approved by a steward") });
+                }
+            }
+        }
+    }
+    // apply from the end of each section backwards so earlier offsets stay valid
+    approved.sort_by(|a, b| b.comment.start_offset.cmp(&a.comment.start_offset));
+
+    let mut applied = vec![];
+    let mut applied_from: BTreeMap<String, usize> = BTreeMap::new(); // lowest offset applied so far in each section
+    for comment in approved {
+        let c = &comment.comment;
+        if let Some(start) = applied_from.get(&c.section) {
+            if c.end_offset > *start {
+                skipped.push(SkippedComment { hash: comment.hash, reason: String::from("overlaps another applied suggestion") });
+                continue;
+            }
+        }
+        let section = match document.content.iter_mut().find(|s| s.name == c.section) {
+            Some(section) => section,
+            None => {
+                skipped.push(SkippedComment { hash: comment.hash, reason: String::from("section not found") });
+                continue;
+            }
+        };
+        match splice_utf16(&section.content, c.start_offset, c.end_offset, c.suggestion.as_deref().unwrap_or("")) {
+            Some(content) => {
+                section.content = content;
+                applied_from.insert(c.section.clone(), c.start_offset);
+                applied.push(comment.hash);
+            }
+            None => skipped.push(SkippedComment { hash: comment.hash, reason: String::from("offsets out of range") }),
+        }
+    }
+
+    if applied.is_empty() {
+        return Ok(ApplyCommentsOutput { hash: None, applied, skipped });
+    }
+    document.meta.upgrade();
+    document.meta.applied_comments = Some(applied.iter().map(|h| h.to_string()).collect());
+    check_expected_head(document_hash.clone(), document_hash.into())?;
+    let hash = _update_document(record.action_address().clone(), document_path(&document)?, &document)?;
+    Ok(ApplyCommentsOutput {
+        hash: Some(hash),
+        applied,
+        skipped,
+    })
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReanchorCommentsInput {
+    pub old_rev: EntryHash,
+    pub new_rev: EntryHash,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReanchorResult {
+    pub comment: EntryHashB64,
+    pub section: String,
+    pub start_offset: usize,
+    pub end_offset: usize,
+    pub orphaned: bool,
+}
+
+/// finds each comment of old_rev in new_rev by its quoted text and links it from new_rev
+/// with its new offsets, comments whose text is gone are linked as orphaned
+#[hdk_extern]
+pub fn reanchor_comments(input: ReanchorCommentsInput) -> ExternResult<Vec<ReanchorResult>> {
+    let old_doc = get_document_entry(input.old_rev.clone())?;
+    let new_doc = get_document_entry(input.new_rev.clone())?;
+    let already: Vec<AnyLinkableHash> = get_links(input.new_rev.clone(), LinkTypes::Comment, None)?
+        .into_iter()
+        .map(|l| l.target)
+        .collect();
+    let mut results = vec![];
+    for comment in _get_comments(input.old_rev.clone())? {
+        let c = &comment.comment;
+        let target: AnyLinkableHash = EntryHash::from(comment.hash.clone()).into();
+        if already.contains(&target) {
+            continue;
+        }
+        // comments made before anchors were stored get their quote from the old revision
+        let anchor = c.anchor.clone().or_else(|| {
+            old_doc.content.iter()
+                .find(|s| s.name == c.section)
+                .and_then(|s| CommentAnchor::from_text(&s.content, c.start_offset, c.end_offset))
+        });
+        let location = match (&anchor, new_doc.content.iter().find(|s| s.name == c.section)) {
+            (Some(anchor), Some(section)) => anchor.locate(&section.content, c.start_offset),
+            _ => None,
+        };
+        let placement = match location {
+            Some((start_offset, end_offset)) => CommentPlacement { start_offset, end_offset, orphaned: false },
+            None => CommentPlacement { start_offset: c.start_offset, end_offset: c.end_offset, orphaned: true },
+        };
+        create_link(input.new_rev.clone(), target, LinkTypes::Comment, placement.to_tag()?)?;
+        results.push(ReanchorResult {
+            comment: comment.hash.clone(),
+            section: c.section.clone(),
+            start_offset: placement.start_offset,
+            end_offset: placement.end_offset,
+            orphaned: placement.orphaned,
+        });
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchor(quote: &str, prefix: &str, suffix: &str) -> CommentAnchor {
+        CommentAnchor { quote: String::from(quote), prefix: String::from(prefix), suffix: String::from(suffix) }
+    }
+
+    #[test]
+    fn anchor_from_text() {
+        let text = "a".repeat(40) + "quote" + &"b".repeat(40);
+        let found = CommentAnchor::from_text(&text, 40, 45).unwrap();
+        assert_eq!(found, anchor("quote", &"a".repeat(ANCHOR_CONTEXT), &"b".repeat(ANCHOR_CONTEXT)));
+        assert_eq!(CommentAnchor::from_text("short", 1, 2), Some(anchor("h", "s", "ort")));
+        assert_eq!(CommentAnchor::from_text("short", 3, 2), None);
+        assert_eq!(CommentAnchor::from_text("short", 2, 9), None);
+    }
+
+    #[test]
+    fn offsets_count_utf16_code_units() {
+        // the emoji takes two code units
+        let text = "x😀 quote";
+        let found = CommentAnchor::from_text(text, 4, 9).unwrap();
+        assert_eq!(found, anchor("quote", "x😀 ", ""));
+        assert_eq!(found.locate(text, 4), Some((4, 9)));
+        assert_eq!(splice_utf16(text, 4, 9, "text"), Some(String::from("x😀 text")));
+        assert_eq!(splice_utf16(text, 4, 10, "text"), None);
+    }
+
+    #[test]
+    fn locate_moved_quote() {
+        let a = anchor("fox", "the quick ", " jumps");
+        assert_eq!(a.locate("the quick fox jumps", 10), Some((10, 13)));
+        // text was inserted in front of it
+        assert_eq!(a.locate("Lo, the quick fox jumps", 10), Some((14, 17)));
+        // the quote itself was changed
+        assert_eq!(a.locate("the quick dog jumps", 10), None);
+    }
+
+    #[test]
+    fn locate_prefers_matching_context_then_proximity() {
+        let a = anchor("fox", "red ", "");
+        assert_eq!(a.locate("a brown fox and a red fox", 0), Some((22, 25)));
+        let a = anchor("fox", "", "");
+        assert_eq!(a.locate("fox fox fox", 5), Some((4, 7)));
+        assert_eq!(a.locate("fox fox fox", 9), Some((8, 11)));
+    }
+
+    #[test]
+    fn locate_insertion_point() {
+        // an insertion point between "before " and "after"
+        let a = anchor("", "before ", "after");
+        assert_eq!(a.locate("before after", 7), Some((7, 7)));
+        assert_eq!(a.locate("new text before after", 7), Some((16, 16)));
+        // with one side changed it stays next to the side that is intact
+        assert_eq!(a.locate("before later", 0), Some((7, 7)));
+        assert_eq!(a.locate("ahead after", 0), Some((6, 6)));
+        // nothing of the context is left
+        assert_eq!(a.locate("something else", 3), None);
+        // without any context it stays where it was as far as the text allows
+        assert_eq!(anchor("", "", "").locate("abc", 2), Some((2, 2)));
+        assert_eq!(anchor("", "", "").locate("abc", 7), Some((3, 3)));
+    }
+}
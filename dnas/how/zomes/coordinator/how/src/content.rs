@@ -0,0 +1,75 @@
+pub use hdk::prelude::*;
+use how_integrity::{check_json_schema, ContentType, Document, Section, Unit};
+
+use crate::document::{SECTION_TYPE_CONTENT, SECTION_TYPE_PROCESS};
+use crate::error::*;
+use crate::template::definitions_from_hierarchy;
+use crate::unit::get_unit_entry;
+
+// a JSON schema named by a content type lives in a process section of one of the unit's
+// processes, those in pending are of documents about to be written along with the unit
+fn find_schema(unit: &Unit, name: &str, pending: &[(&str, &[Section])]) -> HowResult<serde_json::Value> {
+    for (process_type, process_name) in unit.processes.iter() {
+        let process_path = format!("{}.{}", process_type, process_name);
+        let mut sections: Vec<Section> = definitions_from_hierarchy(&process_path, 2, SECTION_TYPE_PROCESS)?
+            .into_iter()
+            .map(|inherited| inherited.section)
+            .collect();
+        for (path, pending_sections) in pending.iter() {
+            // the same walk as definitions_from_hierarchy, i.e. from two segments down
+            let along = *path == process_path || process_path.starts_with(&format!("{}.", path));
+            if along && path.split(".").count() >= 2 {
+                sections.extend(pending_sections.iter().filter(|s| s.section_type == SECTION_TYPE_PROCESS).cloned());
+            }
+        }
+        if let Some(section) = sections.iter().find(|s| s.name == name) {
+            return serde_json::from_str(&section.content).map_err(|e| HowError::InvalidContent {
+                section: String::from(name),
+                reason: format!("schema is not valid JSON: {}", e),
+            });
+        }
+    }
+    Err(HowError::InvalidContent { section: String::from(name), reason: String::from("schema not found") })
+}
+
+fn check_content(section: &Section, schema_for: &dyn Fn(&str) -> HowResult<serde_json::Value>) -> HowResult<()> {
+    let invalid = |reason: String| HowError::InvalidContent { section: section.name.clone(), reason };
+    let content_type = ContentType::parse(&section.content_type);
+    content_type.validate(&section.content).map_err(|reason| invalid(reason))?;
+    if let ContentType::Json { schema: Some(schema) } = &content_type {
+        if !section.content.trim().is_empty() {
+            let schema = schema_for(schema)?;
+            let value: serde_json::Value = serde_json::from_str(&section.content).unwrap_or(serde_json::Value::Null);
+            check_json_schema(&value, &schema, &section.name).map_err(|reason| invalid(reason))?;
+        }
+    }
+    Ok(())
+}
+
+fn normalize_with(document: &mut Document, schema_for: &dyn Fn(&str) -> HowResult<serde_json::Value>) -> HowResult<()> {
+    let mut normalized = document.content.clone();
+    for section in normalized.iter_mut().filter(|s| s.section_type == SECTION_TYPE_CONTENT) {
+        check_content(section, schema_for)?;
+        section.content = ContentType::parse(&section.content_type).normalize(&section.content);
+    }
+    document.content = normalized;
+    Ok(())
+}
+
+/// checks a section of document against its content type, including any JSON schema
+pub fn check_section(document: &Document, section: &Section) -> HowResult<()> {
+    check_content(section, &|name: &str| find_schema(&get_unit_entry(document.unit_hash.clone())?, name, &[]))
+}
+
+/// checks the content sections of a document against their content types, including any
+/// JSON schemas, and puts them into canonical form
+pub fn normalize_document(document: &mut Document) -> HowResult<()> {
+    let unit_hash = document.unit_hash.clone();
+    normalize_with(document, &|name: &str| find_schema(&get_unit_entry(unit_hash.clone())?, name, &[]))
+}
+
+/// normalize_document for a document of a unit that may not have been written yet, whose
+/// schemas may be in the pending documents (path and sections) written along with it
+pub fn normalize_pending_document(document: &mut Document, unit: &Unit, pending: &[(&str, &[Section])]) -> HowResult<()> {
+    normalize_with(document, &|name: &str| find_schema(unit, name, pending))
+}
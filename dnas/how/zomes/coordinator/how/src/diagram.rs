@@ -0,0 +1,141 @@
+use std::collections::{BTreeSet, HashMap};
+
+pub use hdk::prelude::*;
+use how_integrity::Unit;
+
+use crate::tree::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum DiagramFormat {
+    Dot,
+    Mermaid,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeDiagramInput {
+    pub format: DiagramFormat,
+    #[serde(default)]
+    pub root: String,
+}
+
+struct DiagramNode {
+    id: String,
+    short_name: String,
+    state: String,
+}
+
+struct DiagramEdge {
+    from: String,
+    to: String,
+    label: Option<String>,
+}
+
+/// renders the tree below root as a Graphviz DOT or Mermaid diagram
+#[hdk_extern]
+pub fn get_tree_diagram(input: TreeDiagramInput) -> ExternResult<String> {
+    let resolved = resolve_path(input.root)?;
+    let tree = _get_tree(tree_path(resolved.path))?;
+    let (nodes, edges) = diagram_graph(&tree)?;
+    Ok(match input.format {
+        DiagramFormat::Dot => render_dot(&nodes, &edges),
+        DiagramFormat::Mermaid => render_mermaid(&nodes, &edges),
+    })
+}
+
+// each unit gets a single node no matter how many parents it appears under, and
+// edges follow the tree, so every DAG parent gets its own edge
+fn diagram_graph(tree: &Tree<Content>) -> ExternResult<(Vec<DiagramNode>, Vec<DiagramEdge>)> {
+    let mut ids: HashMap<EntryHash, String> = HashMap::new();
+    let mut nodes = vec![];
+    let mut units = vec![];
+    for node in tree.tree.iter() {
+        for info in node.val.units.iter() {
+            if ids.contains_key(&info.hash) {
+                continue;
+            }
+            let id = format!("u{}", ids.len());
+            ids.insert(info.hash.clone(), id.clone());
+            let record = get(info.hash.clone(), GetOptions::default())?
+                .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Unit not found"))))?;
+            let unit: Unit = record
+                .entry()
+                .to_app_option().map_err(|err| wasm_error!(err))?
+                .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Malformed unit"))))?;
+            nodes.push(DiagramNode {
+                id,
+                short_name: unit.short_name.clone(),
+                state: info.state.clone(),
+            });
+            units.push((info.hash.clone(), unit));
+        }
+    }
+
+    let mut seen: BTreeSet<(String, String)> = BTreeSet::new();
+    let mut edges = vec![];
+    for node in tree.tree.iter() {
+        for child in node.children.iter() {
+            for parent_info in node.val.units.iter() {
+                for child_info in tree.tree[*child].val.units.iter() {
+                    let from = ids[&parent_info.hash].clone();
+                    let to = ids[&child_info.hash].clone();
+                    if seen.insert((from.clone(), to.clone())) {
+                        edges.push(DiagramEdge { from, to, label: None });
+                    }
+                }
+            }
+        }
+    }
+
+    // supersede and fork relationships are recorded in the unit's history, they only
+    // get drawn if the unit they point to is part of the diagram
+    for (hash, unit) in units.iter() {
+        for (relation, target) in unit.history.iter() {
+            if let Some(to) = ids.get(&EntryHash::from(target.clone())) {
+                edges.push(DiagramEdge {
+                    from: ids[hash].clone(),
+                    to: to.clone(),
+                    label: Some(relation.clone()),
+                });
+            }
+        }
+    }
+    Ok((nodes, edges))
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn render_dot(nodes: &[DiagramNode], edges: &[DiagramEdge]) -> String {
+    let mut out = String::from("digraph how {\n  node [shape=box];\n");
+    for node in nodes {
+        out.push_str(&format!("  {} [label=\"{}\\n{}\"];\n", node.id, dot_escape(&node.short_name), dot_escape(&node.state)));
+    }
+    for edge in edges {
+        match &edge.label {
+            None => out.push_str(&format!("  {} -> {};\n", edge.from, edge.to)),
+            Some(label) => out.push_str(&format!("  {} -> {} [style=dashed, label=\"{}\"];\n", edge.from, edge.to, dot_escape(label))),
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;").replace('|', "#124;")
+}
+
+fn render_mermaid(nodes: &[DiagramNode], edges: &[DiagramEdge]) -> String {
+    let mut out = String::from("graph TD\n");
+    for node in nodes {
+        out.push_str(&format!("  {}[\"{}<br/>{}\"]\n", node.id, mermaid_escape(&node.short_name), mermaid_escape(&node.state)));
+    }
+    for edge in edges {
+        match &edge.label {
+            None => out.push_str(&format!("  {} --> {}\n", edge.from, edge.to)),
+            Some(label) => out.push_str(&format!("  {} -.->|{}| {}\n", edge.from, mermaid_escape(label), edge.to)),
+        }
+    }
+    out
+}
@@ -0,0 +1,262 @@
+pub use hdk::prelude::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct DiffHunk {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Default)]
+pub enum DiffGranularity {
+    #[default]
+    Line,
+    Word,
+}
+
+// splits text into tokens that concatenate back into exactly the original text
+fn tokenize<'a>(text: &'a str, granularity: &DiffGranularity) -> Vec<&'a str> {
+    match granularity {
+        DiffGranularity::Line => text.split_inclusive('\n').collect(),
+        DiffGranularity::Word => {
+            let mut tokens = vec![];
+            let mut start = 0;
+            let mut in_space: Option<bool> = None;
+            for (i, c) in text.char_indices() {
+                let space = c.is_whitespace();
+                if in_space.is_some() && in_space != Some(space) {
+                    tokens.push(&text[start..i]);
+                    start = i;
+                }
+                in_space = Some(space);
+            }
+            if start < text.len() {
+                tokens.push(&text[start..]);
+            }
+            tokens
+        }
+    }
+}
+
+// beyond this many token pairs the part of two texts between what they share at either
+// end isn't compared token by token, it's reported as replaced as a whole instead
+const MAX_COMPARED_PAIRS: usize = 250_000;
+
+// classic longest common subsequence table, lcs[i][j] is the lcs length of a[i..] and b[j..]
+fn lcs_table(a: &[&str], b: &[&str]) -> Vec<Vec<usize>> {
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    lcs
+}
+
+// the lcs length alone only needs two rows of the table
+fn lcs_length(a: &[&str], b: &[&str]) -> usize {
+    let mut next = vec![0usize; b.len() + 1];
+    let mut row = vec![0usize; b.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            row[j] = if a[i] == b[j] { next[j + 1] + 1 } else { next[j].max(row[j + 1]) };
+        }
+        std::mem::swap(&mut row, &mut next);
+    }
+    next[0]
+}
+
+// how many tokens a and b have in common at their start and, after that, at their end
+fn common_affixes(a: &[&str], b: &[&str]) -> (usize, usize) {
+    let prefix = a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..].iter().rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    (prefix, suffix)
+}
+
+fn push_hunk(hunks: &mut Vec<DiffHunk>, op: DiffOp, token: &str) {
+    match hunks.last_mut() {
+        Some(last) if last.op == op => last.text.push_str(token),
+        _ => hunks.push(DiffHunk { op, text: String::from(token) }),
+    }
+}
+
+/// returns the hunks that turn from into to, consecutive tokens with the same op are grouped
+pub fn diff_text(from: &str, to: &str, granularity: &DiffGranularity) -> Vec<DiffHunk> {
+    let a = tokenize(from, granularity);
+    let b = tokenize(to, granularity);
+    let (prefix, suffix) = common_affixes(&a, &b);
+    let mut hunks = vec![];
+    for token in &a[..prefix] {
+        push_hunk(&mut hunks, DiffOp::Equal, token);
+    }
+    let a_changed = &a[prefix..a.len() - suffix];
+    let b_changed = &b[prefix..b.len() - suffix];
+    if a_changed.len() * b_changed.len() > MAX_COMPARED_PAIRS {
+        for token in a_changed {
+            push_hunk(&mut hunks, DiffOp::Delete, token);
+        }
+        for token in b_changed {
+            push_hunk(&mut hunks, DiffOp::Insert, token);
+        }
+    } else {
+        diff_tokens(a_changed, b_changed, &mut hunks);
+    }
+    for token in &a[a.len() - suffix..] {
+        push_hunk(&mut hunks, DiffOp::Equal, token);
+    }
+    hunks
+}
+
+fn diff_tokens(a: &[&str], b: &[&str], hunks: &mut Vec<DiffHunk>) {
+    let lcs = lcs_table(a, b);
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            push_hunk(hunks, DiffOp::Equal, a[i]);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            push_hunk(hunks, DiffOp::Delete, a[i]);
+            i += 1;
+        } else {
+            push_hunk(hunks, DiffOp::Insert, b[j]);
+            j += 1;
+        }
+    }
+    for token in &a[i..] {
+        push_hunk(hunks, DiffOp::Delete, token);
+    }
+    for token in &b[j..] {
+        push_hunk(hunks, DiffOp::Insert, token);
+    }
+}
+
+/// how much of two texts is shared, from 0.0 (nothing) to 1.0 (identical).  Texts too
+/// different to compare in full only count what they share at either end.
+pub fn similarity(a: &str, b: &str) -> f32 {
+    let a = tokenize(a, &DiffGranularity::Word);
+    let b = tokenize(b, &DiffGranularity::Word);
+    if a.len() + b.len() == 0 {
+        return 1.0;
+    }
+    let (prefix, suffix) = common_affixes(&a, &b);
+    let a_changed = &a[prefix..a.len() - suffix];
+    let b_changed = &b[prefix..b.len() - suffix];
+    let mut shared = prefix + suffix;
+    if a_changed.len() * b_changed.len() <= MAX_COMPARED_PAIRS {
+        shared += lcs_length(a_changed, b_changed);
+    }
+    (2 * shared) as f32 / (a.len() + b.len()) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(op: DiffOp, text: &str) -> DiffHunk {
+        DiffHunk { op, text: String::from(text) }
+    }
+
+    // the hunks have to reproduce both texts
+    fn sides(hunks: &[DiffHunk]) -> (String, String) {
+        let mut from = String::new();
+        let mut to = String::new();
+        for h in hunks {
+            match h.op {
+                DiffOp::Equal => {
+                    from.push_str(&h.text);
+                    to.push_str(&h.text);
+                }
+                DiffOp::Delete => from.push_str(&h.text),
+                DiffOp::Insert => to.push_str(&h.text),
+            }
+        }
+        (from, to)
+    }
+
+    #[test]
+    fn diff_lines() {
+        let hunks = diff_text("a\nb\nc\n", "a\nx\nc\n", &DiffGranularity::Line);
+        assert_eq!(hunks, vec![
+            hunk(DiffOp::Equal, "a\n"),
+            hunk(DiffOp::Delete, "b\n"),
+            hunk(DiffOp::Insert, "x\n"),
+            hunk(DiffOp::Equal, "c\n"),
+        ]);
+        // a last line without a newline is a line of its own
+        let hunks = diff_text("a\nb", "a\nb\n", &DiffGranularity::Line);
+        assert_eq!(hunks, vec![hunk(DiffOp::Equal, "a\n"), hunk(DiffOp::Delete, "b"), hunk(DiffOp::Insert, "b\n")]);
+    }
+
+    #[test]
+    fn diff_words() {
+        let hunks = diff_text("the quick fox", "the slow fox", &DiffGranularity::Word);
+        assert_eq!(hunks, vec![
+            hunk(DiffOp::Equal, "the "),
+            hunk(DiffOp::Delete, "quick"),
+            hunk(DiffOp::Insert, "slow"),
+            hunk(DiffOp::Equal, " fox"),
+        ]);
+    }
+
+    #[test]
+    fn diff_edges() {
+        assert_eq!(diff_text("", "", &DiffGranularity::Line), vec![]);
+        assert_eq!(diff_text("", "new", &DiffGranularity::Line), vec![hunk(DiffOp::Insert, "new")]);
+        assert_eq!(diff_text("old", "", &DiffGranularity::Word), vec![hunk(DiffOp::Delete, "old")]);
+        assert_eq!(diff_text("same\n", "same\n", &DiffGranularity::Line), vec![hunk(DiffOp::Equal, "same\n")]);
+    }
+
+    #[test]
+    fn hunks_reproduce_both_texts() {
+        let from = "one two  three\nfour\n\nfive ünïcode";
+        let to = "one three\nfour five\n\nsix ünïcode\n";
+        for granularity in [DiffGranularity::Line, DiffGranularity::Word] {
+            let (a, b) = sides(&diff_text(from, to, &granularity));
+            assert_eq!(a, from);
+            assert_eq!(b, to);
+        }
+    }
+
+    #[test]
+    fn similarity_of_texts() {
+        assert_eq!(similarity("", ""), 1.0);
+        assert_eq!(similarity("a b c", "a b c"), 1.0);
+        assert_eq!(similarity("a b c", "x y z"), 0.4); // only the spaces are shared
+        assert!(similarity("the quick brown fox", "the quick brown dog") > 0.6);
+    }
+
+    #[test]
+    fn lcs_length_matches_the_table() {
+        let a = tokenize("one two three four five", &DiffGranularity::Word);
+        let b = tokenize("two four six five one", &DiffGranularity::Word);
+        assert_eq!(lcs_length(&a, &b), lcs_table(&a, &b)[0][0]);
+        assert_eq!(lcs_length(&a, &[]), 0);
+    }
+
+    #[test]
+    fn large_changes_are_replaced_as_a_whole() {
+        let from: String = (0..600).map(|i| format!("a{}\n", i)).collect();
+        let to: String = (0..600).map(|i| format!("b{}\n", i)).collect();
+        let from = format!("head\n{}tail\n", from);
+        let to = format!("head\n{}tail\n", to);
+        let hunks = diff_text(&from, &to, &DiffGranularity::Line);
+        assert_eq!(hunks.iter().map(|h| h.op.clone()).collect::<Vec<DiffOp>>(), vec![DiffOp::Equal, DiffOp::Delete, DiffOp::Insert, DiffOp::Equal]);
+        assert_eq!(sides(&hunks), (from.clone(), to.clone()));
+        // only the shared ends count towards how similar they are
+        assert_eq!(similarity(&from, &to), 10.0 / 2408.0);
+    }
+}
@@ -1,23 +1,35 @@
+use std::collections::BTreeMap;
+
 use crate::error::*;
 use crate::tree::*;
 pub use hdk::prelude::Path;
 pub use hdk::prelude::*;
 use hdk::prelude::holo_hash::AgentPubKeyB64;
 use holo_hash::{EntryHashB64};
-use how_integrity::{Document, EntryTypes, LinkTypes};
+use how_integrity::{Document, Section, Unit, EntryTypes, LinkTypes};
 use crate::utils::*;
+use crate::diff::*;
+use crate::content::normalize_document;
+use crate::export::{provenance_by, Provenance};
 
+pub const DOC_TREE_META: &str = "_";
 pub const DOC_DOCUMENT: &str = "_document";
 pub const DOC_COMMENT: &str = "_comment";
+pub const DOC_TEMPLATE: &str = "_template";
+pub const DOC_COLLECTION: &str = "_collection";
 
 pub const SECTION_TYPE_PROCESS: &str = "p";
 pub const SECTION_TYPE_REQUIREMENT: &str = "r";
-pub const SECTION_TYPE_CONTENT: &str = "";
+pub use how_integrity::SECTION_TYPE_CONTENT;
+pub const SECTION_TYPE_COLLECTION_DEF: &str = "d";
 
 pub const SECTION_SRC_PROCESS: &str = "p";
 pub const SECTION_SRC_REQUIREMENT: &str = "r";
 pub const SECTION_SRC_MANUAL: &str = "m";
 
+// source path of sections that were entered by hand rather than copied from the tree
+pub const SOURCE_MANUAL: &str = "_manual";
+
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct DocumentInput {
@@ -25,30 +37,97 @@ pub struct DocumentInput {
     pub document: Document,
 }
 
-fn link_document(hash: EntryHash, path: String) -> ExternResult<()> {
+// document links are tagged with the document type so that the type can be known without
+// having to get the document
+pub fn link_document(hash: EntryHash, path: String, document_type: &str) -> ExternResult<()> {
     let path = tree_path(path);
 //    if !path.exists()? {
 //        return Err(HowError::MissingPath.into());
 //    }
     let anchor_hash = path.path_entry_hash()?;
-    create_link(anchor_hash, hash, LinkTypes::Document, ())?;
+    create_link(anchor_hash, hash, LinkTypes::Document, LinkTag::new(String::from(document_type)))?;
     Ok(())
 }
 
 #[hdk_extern]
 pub fn create_document(input: DocumentInput) -> ExternResult<EntryHashB64> {
-    let _action_hash = create_entry(EntryTypes::Document(input.document.clone()))?;
-    let hash = hash_entry(&input.document)?;
-    link_document(hash.clone(), input.path)?;
+    let mut document = input.document;
+    normalize_document(&mut document)?;
+    let _action_hash = create_entry(EntryTypes::Document(document.clone()))?;
+    let hash = hash_entry(&document)?;
+    link_document(hash.clone(), input.path, &document.document_type)?;
+    if document.document_type == DOC_DOCUMENT {
+        create_link(document.unit_hash.clone(), hash.clone(), LinkTypes::Head, ())?;
+    }
     Ok(hash.into())
 }
 
+pub fn delete_head_links(unit_hash: EntryHash, hash: &EntryHash) -> ExternResult<()> {
+    let any: AnyLinkableHash = hash.clone().into();
+    for l in get_links(unit_hash, LinkTypes::Head, None)? {
+        if l.target == any {
+            delete_link(l.create_link_hash)?;
+        }
+    }
+    Ok(())
+}
+
+pub const MARK_TYPE_COMMENT_STATUS: u8 = 1;
+pub const MARK_TYPE_VOTE: u8 = 2;
+pub const MARK_TYPE_APPROVAL: u8 = 3;
+pub const MARK_TYPE_ATTACHMENT: u8 = 4;
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Mark {
-    mark_type: u8,
-    mark: String,
-    author: AgentPubKeyB64,
+    pub mark_type: u8,
+    pub mark: String,
+    pub author: AgentPubKeyB64,
+}
+
+/// a mark with when it was made and the link that holds it, for when the order of
+/// marks matters.  The author is always whoever made the link, imported marks carry the
+/// author and time they were exported with as provenance, which is only informational.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkDetail {
+    pub mark_type: u8,
+    pub mark: String,
+    pub author: AgentPubKeyB64,
+    pub timestamp: Timestamp,
+    pub link: ActionHash,
+    pub provenance: Option<Provenance>,
+}
+
+// the marks on hash that haven't been deleted
+pub fn get_mark_details(hash: EntryHash) -> HowResult<Vec<MarkDetail>> {
+    let mut marks = vec![];
+    for (create, deletes) in get_link_details(hash, LinkTypes::Mark, None)?.into_inner() {
+        if !deletes.is_empty() {
+            continue;
+        }
+        let link = match CreateLink::try_from(create.action().clone()) {
+            Ok(link) => link,
+            Err(_) => continue,
+        };
+        let mut tag_bytes = link.tag.into_inner();
+        let mark_type = match tag_bytes.pop() {
+            Some(mark_type) => mark_type,
+            None => continue,
+        };
+        let mark = String::from_utf8(tag_bytes)
+            .map_err(|_e| wasm_error!(WasmErrorInner::Guest(String::from("could not convert link tag to string"))))?;
+        let provenance = provenance_by(create.hashed.hash.clone().into(), Some(create.action().author()))?;
+        marks.push(MarkDetail {
+            mark_type,
+            mark,
+            author: create.action().author().clone().into(),
+            timestamp: create.action().timestamp(),
+            link: create.hashed.hash.clone(),
+            provenance,
+        });
+    }
+    Ok(marks)
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -63,14 +142,27 @@ pub struct DocumentOutput {
     pub marks: Vec<Mark>,
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentsOutput {
+    pub path: String,
+    pub redirected_from: Option<String>,
+    pub documents: Vec<DocumentOutput>,
+}
+
 #[hdk_extern]
-pub fn get_documents(input: String) -> ExternResult<Vec<DocumentOutput>> {
-    let path = tree_path(input);
+pub fn get_documents(input: String) -> ExternResult<DocumentsOutput> {
+    let resolved = resolve_path(input)?;
+    let path = tree_path(resolved.path.clone());
     let documents = get_documents_inner(path.path_entry_hash()?)?;
-    Ok(documents)
+    Ok(DocumentsOutput {
+        path: resolved.path,
+        redirected_from: resolved.redirected_from,
+        documents,
+    })
 }
 
-fn get_documents_inner(base: EntryHash) -> HowResult<Vec<DocumentOutput>> {
+pub fn get_documents_inner(base: EntryHash) -> HowResult<Vec<DocumentOutput>> {
     let links = get_links(base, LinkTypes::Document, None)?;
 
     let mut get_input=  vec!();
@@ -94,7 +186,7 @@ pub fn get_document(hash: EntryHash) -> ExternResult<DocumentOutput> {
     Ok(result[0].clone())
 }
 
-fn _get_docs(get_input: Vec<GetInput>) -> HowResult<Vec<DocumentOutput>> {
+pub fn _get_docs(get_input: Vec<GetInput>) -> HowResult<Vec<DocumentOutput>> {
 
     let document_elements = HDK.with(|hdk| hdk.borrow().get_details(get_input))?;
 
@@ -138,34 +230,143 @@ fn _get_docs(get_input: Vec<GetInput>) -> HowResult<Vec<DocumentOutput>> {
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdateDocumentInput {
     pub hash: EntryHashB64,
     pub path: String,
     pub document: Document,
+    #[serde(default)]
+    pub expected_head: Option<EntryHashB64>, // if set the update is rejected unless this is still the only head
 }
 #[hdk_extern]
 pub fn update_document(input: UpdateDocumentInput) -> ExternResult<EntryHashB64> {
+    if let Some(expected_head) = input.expected_head.clone() {
+        check_expected_head(EntryHash::from(input.hash.clone()), expected_head)?;
+    }
     let record = get(EntryHash::from(input.hash), GetOptions::default())?
         .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Document not found"))))?;
-    _update_document(record.action_address().clone().into(),input.path, &input.document)
+    let mut document = input.document;
+    normalize_document(&mut document)?;
+    _update_document(record.action_address().clone().into(),input.path, &document)
+}
+
+// rejects an update of hash unless hash hasn't been updated yet and expected_head is
+// still the only head, otherwise the update would fork the document
+pub fn check_expected_head(hash: EntryHash, expected_head: EntryHashB64) -> HowResult<()> {
+    let details = get_entry_details(hash.clone())?.ok_or(HowError::DocumentNotFound)?;
+    if !details.updates.is_empty() {
+        return Err(HowError::StaleHead);
+    }
+    if _get_document_heads(hash)? != vec![expected_head] {
+        return Err(HowError::StaleHead);
+    }
+    Ok(())
 }
 
 pub fn _update_document(hash: ActionHash, path: String, document: &Document) -> ExternResult<EntryHashB64> {
+    update_document_revision(hash, path, document, true)
+}
+
+// The new revision replaces the one it updates as a head of the unit.  An update built
+// on a revision that was already updated forks the document and becomes a head of its
+// own next to the existing one.  Reparenting carries every revision along, there only
+// the revision that was a head before becomes one again.
+pub fn update_document_revision(hash: ActionHash, path: String, document: &Document, as_head: bool) -> ExternResult<EntryHashB64> {
+    let old_record = get(hash.clone(), GetOptions::default())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Document not found"))))?;
+    let old_hash = old_record.action().entry_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Document not found"))))?.clone();
+    let old_document: Option<Document> = old_record.entry().to_app_option().map_err(|e| wasm_error!(e))?;
     let _action_hash = update_entry(hash, document)?;
     let hash = hash_entry(document)?;
     // TODO validate that old doc had the same path, or get the path some other way?
-    link_document(hash.clone(), path)?;
+    link_document(hash.clone(), path, &document.document_type)?;
+    if let Some(old_document) = old_document {
+        delete_head_links(old_document.unit_hash, &old_hash)?;
+    }
+    if as_head && document.document_type == DOC_DOCUMENT {
+        create_link(document.unit_hash.clone(), hash.clone(), LinkTypes::Head, ())?;
+    }
     return Ok(hash.into());
 }
 
 #[hdk_extern]
 pub fn delete_document(input: ActionHash) -> ExternResult<ActionHash> {
-    let _record = get(input.clone(), GetOptions::default())?
+    let record = get(input.clone(), GetOptions::default())?
         .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Document not found"))))?;
     let action_hash = delete_entry(input)?;
+    let document: Option<Document> = record.entry().to_app_option().map_err(|e| wasm_error!(e))?;
+    if let (Some(hash), Some(document)) = (record.action().entry_hash(), document) {
+        delete_head_links(document.unit_hash, hash)?;
+    }
     Ok(action_hash)
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(tag = "type", content = "content")]
+pub enum CurrentDocument {
+    Head(DocumentOutput),
+    Conflict(Vec<DocumentOutput>),
+}
+
+// the unit's head documents, normally just one, more if it was updated concurrently
+pub fn get_head_documents(unit_hash: EntryHash) -> HowResult<Vec<DocumentOutput>> {
+    let links = get_links(unit_hash.clone(), LinkTypes::Head, None)?;
+    let mut targets = vec![];
+    let mut get_input = vec![];
+    for link in links {
+        if !targets.contains(&link.target) {
+            targets.push(link.target.clone());
+            if let Ok(hash) = AnyDhtHash::try_from(link.target) {
+                get_input.push(GetInput::new(hash, GetOptions::default()))
+            }
+        }
+    }
+    let mut heads: Vec<DocumentOutput> = _get_docs(get_input)?
+        .into_iter()
+        .filter(|d| d.updated_by.is_empty() && d.deleted_by.is_empty())
+        .collect();
+    if heads.is_empty() {
+        // documents created before there were head links have to be found at the unit's path
+        let record = get(unit_hash.clone(), GetOptions::default())?
+            .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Unit not found"))))?;
+        let unit: Unit = record
+            .entry()
+            .to_app_option()?
+            .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Malformed unit"))))?;
+        heads = get_documents_inner(tree_path(unit.path_str()?).path_entry_hash()?)?
+            .into_iter()
+            .filter(|d| d.content.document_type == DOC_DOCUMENT
+                && d.content.unit_hash == unit_hash
+                && d.updated_by.is_empty()
+                && d.deleted_by.is_empty())
+            .collect();
+    }
+    Ok(heads)
+}
+
+pub fn _get_current_document(unit_hash: EntryHash) -> HowResult<CurrentDocument> {
+    let mut heads = get_head_documents(unit_hash)?;
+    match heads.len() {
+        0 => Err(HowError::DocumentNotFound),
+        1 => Ok(CurrentDocument::Head(heads.remove(0))),
+        _ => Ok(CurrentDocument::Conflict(heads)),
+    }
+}
+
+// for callers that need a single document to work from, a conflict is an error
+pub fn get_current_head(unit_hash: EntryHash) -> HowResult<DocumentOutput> {
+    match _get_current_document(unit_hash)? {
+        CurrentDocument::Head(doc) => Ok(doc),
+        CurrentDocument::Conflict(_) => Err(HowError::DocumentConflict),
+    }
+}
+
+#[hdk_extern]
+pub fn get_current_document(unit_hash: EntryHash) -> ExternResult<CurrentDocument> {
+    Ok(_get_current_document(unit_hash)?)
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MarkDocumentInput {
@@ -189,3 +390,297 @@ pub fn mark_document(marks: Vec<MarkDocumentInput>) -> ExternResult<Vec<ActionHa
     }
     Ok(results)
 }
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentRevision {
+    pub hash: EntryHashB64,
+    pub previous: Vec<EntryHashB64>,
+    pub updated_by: Vec<EntryHashB64>,
+    pub author: AgentPubKeyB64,
+    pub timestamp: Timestamp,
+    pub state: String,
+    pub deleted: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentHistory {
+    pub revisions: Vec<DocumentRevision>, // oldest first
+    pub branches: Vec<EntryHashB64>, // revisions that were updated more than once
+}
+
+pub fn get_entry_details(hash: EntryHash) -> HowResult<Option<EntryDetails>> {
+    match get_details(hash, GetOptions::default())? {
+        Some(Details::Entry(details)) => Ok(Some(details)),
+        _ => Ok(None),
+    }
+}
+
+fn revision_from_details(hash: EntryHash, details: &EntryDetails) -> HowResult<DocumentRevision> {
+    let document: Document = details.entry.clone().try_into().map_err(|_e| HowError::DocumentNotFound)?;
+    let mut actions = details.actions.clone();
+    actions.sort_by_key(|a| a.action().timestamp());
+    let first = actions.first().ok_or(HowError::DocumentNotFound)?;
+    let mut previous = vec![];
+    for a in actions.iter() {
+        if let Action::Update(update) = a.action() {
+            let original: EntryHashB64 = update.original_entry_address.clone().into();
+            if !previous.contains(&original) {
+                previous.push(original);
+            }
+        }
+    }
+    let mut updated_by = vec![];
+    for u in details.updates.iter() {
+        if let Some(entry_hash) = u.action().entry_hash() {
+            let entry_hash: EntryHashB64 = entry_hash.clone().into();
+            if !updated_by.contains(&entry_hash) {
+                updated_by.push(entry_hash);
+            }
+        }
+    }
+    Ok(DocumentRevision {
+        hash: hash.into(),
+        previous,
+        updated_by,
+        author: first.action().author().clone().into(),
+        timestamp: first.action().timestamp(),
+        state: document.state,
+        deleted: !details.deletes.is_empty(),
+    })
+}
+
+// walks the update chain backwards through the originals of update actions and
+// forwards through the updates, so any revision gets the whole history
+pub fn _get_document_history(hash: EntryHash) -> HowResult<DocumentHistory> {
+    let mut revisions: Vec<DocumentRevision> = vec![];
+    let mut seen: Vec<EntryHashB64> = vec![hash.clone().into()];
+    let mut queue = vec![hash];
+    while let Some(hash) = queue.pop() {
+        let details = get_entry_details(hash.clone())?.ok_or(HowError::DocumentNotFound)?;
+        let revision = revision_from_details(hash, &details)?;
+        for next in revision.previous.iter().chain(revision.updated_by.iter()) {
+            if !seen.contains(next) {
+                seen.push(next.clone());
+                queue.push(next.clone().into());
+            }
+        }
+        revisions.push(revision);
+    }
+    revisions.sort_by_key(|r| r.timestamp);
+    let branches = revisions.iter()
+        .filter(|r| r.updated_by.len() > 1)
+        .map(|r| r.hash.clone())
+        .collect();
+    Ok(DocumentHistory {
+        revisions,
+        branches,
+    })
+}
+
+/// whether ancestor is the revision itself or one of the revisions it was built on
+pub fn is_revision_ancestor(ancestor: &EntryHash, revision: EntryHash) -> HowResult<bool> {
+    let mut seen: Vec<EntryHash> = vec![];
+    let mut queue = vec![revision];
+    while let Some(hash) = queue.pop() {
+        if hash == *ancestor {
+            return Ok(true);
+        }
+        if seen.contains(&hash) {
+            continue;
+        }
+        seen.push(hash.clone());
+        let details = get_entry_details(hash.clone())?.ok_or(HowError::DocumentNotFound)?;
+        for previous in revision_from_details(hash, &details)?.previous {
+            queue.push(previous.into());
+        }
+    }
+    Ok(false)
+}
+
+#[hdk_extern]
+pub fn get_document_history(hash: EntryHash) -> ExternResult<DocumentHistory> {
+    Ok(_get_document_history(hash)?)
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum SectionChange {
+    Added,
+    Removed,
+    Renamed,
+    Modified,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionDiff {
+    pub name: String,
+    pub previous_name: Option<String>, // only set for renamed sections
+    pub change: SectionChange,
+    pub previous_content_type: Option<String>, // only set if the content type changed
+    pub hunks: Vec<DiffHunk>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MetaChange {
+    pub key: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentDiff {
+    pub from: EntryHashB64,
+    pub to: EntryHashB64,
+    pub sections: Vec<SectionDiff>,
+    pub editors_added: Vec<AgentPubKeyB64>,
+    pub editors_removed: Vec<AgentPubKeyB64>,
+    pub meta: Vec<MetaChange>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffDocumentsInput {
+    pub from_hash: EntryHash,
+    pub to_hash: EntryHash,
+    #[serde(default)]
+    pub granularity: DiffGranularity,
+}
+
+// a removed and an added section are taken to be a rename if their content is mostly the same
+const RENAME_SIMILARITY: f32 = 0.6;
+
+pub fn get_document_entry(hash: EntryHash) -> HowResult<Document> {
+    let record = get(hash, GetOptions::default())?.ok_or(HowError::DocumentNotFound)?;
+    let document: Document = record
+        .entry()
+        .to_app_option()?
+        .ok_or(HowError::DocumentNotFound)?;
+    Ok(document)
+}
+
+#[hdk_extern]
+pub fn diff_documents(input: DiffDocumentsInput) -> ExternResult<DocumentDiff> {
+    let from = get_document_entry(input.from_hash.clone())?;
+    let to = get_document_entry(input.to_hash.clone())?;
+    let mut sections = vec![];
+
+    let mut removed: Vec<&Section> = from.content.iter().filter(|s| !to.content.iter().any(|t| t.name == s.name)).collect();
+    for section in to.content.iter() {
+        match from.content.iter().find(|s| s.name == section.name) {
+            Some(old) => {
+                if old.content != section.content || old.content_type != section.content_type {
+                    sections.push(SectionDiff {
+                        name: section.name.clone(),
+                        previous_name: None,
+                        change: SectionChange::Modified,
+                        previous_content_type: if old.content_type != section.content_type { Some(old.content_type.clone()) } else { None },
+                        hunks: diff_text(&old.content, &section.content, &input.granularity),
+                    });
+                }
+            }
+            None => {
+                // pick the most similar of the removed sections of the same type as the rename source
+                let best = removed.iter().enumerate()
+                    .filter(|(_, s)| s.section_type == section.section_type)
+                    .map(|(i, s)| (i, similarity(&s.content, &section.content)))
+                    .filter(|(_, sim)| *sim >= RENAME_SIMILARITY)
+                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+                match best {
+                    Some((i, _)) => {
+                        let old = removed.remove(i);
+                        sections.push(SectionDiff {
+                            name: section.name.clone(),
+                            previous_name: Some(old.name.clone()),
+                            change: SectionChange::Renamed,
+                            previous_content_type: if old.content_type != section.content_type { Some(old.content_type.clone()) } else { None },
+                            hunks: diff_text(&old.content, &section.content, &input.granularity),
+                        });
+                    }
+                    None => sections.push(SectionDiff {
+                        name: section.name.clone(),
+                        previous_name: None,
+                        change: SectionChange::Added,
+                        previous_content_type: None,
+                        hunks: diff_text("", &section.content, &input.granularity),
+                    }),
+                }
+            }
+        }
+    }
+    for old in removed {
+        sections.push(SectionDiff {
+            name: old.name.clone(),
+            previous_name: None,
+            change: SectionChange::Removed,
+            previous_content_type: None,
+            hunks: diff_text(&old.content, "", &input.granularity),
+        });
+    }
+
+    let editors_added = to.editors.iter().filter(|e| !from.editors.contains(e)).cloned().collect();
+    let editors_removed = from.editors.iter().filter(|e| !to.editors.contains(e)).cloned().collect();
+
+    // meta changes are reported in the stored form, so extension keys show up too
+    let from_meta: BTreeMap<String, String> = from.meta.clone().into();
+    let to_meta: BTreeMap<String, String> = to.meta.clone().into();
+    let mut meta = vec![];
+    for (key, value) in from_meta.iter() {
+        match to_meta.get(key) {
+            Some(new_value) if new_value == value => (),
+            new_value => meta.push(MetaChange { key: key.clone(), from: Some(value.clone()), to: new_value.cloned() }),
+        }
+    }
+    for (key, value) in to_meta.iter() {
+        if !from_meta.contains_key(key) {
+            meta.push(MetaChange { key: key.clone(), from: None, to: Some(value.clone()) });
+        }
+    }
+
+    Ok(DocumentDiff {
+        from: input.from_hash.into(),
+        to: input.to_hash.into(),
+        sections,
+        editors_added,
+        editors_removed,
+        meta,
+    })
+}
+
+// Reparenting a unit copies every revision of its document to the new unit, so a
+// revision nothing updated isn't necessarily a head, only those with a head link from
+// their unit are.  Documents from before there were head links have none at all.
+pub fn _get_document_heads(hash: EntryHash) -> HowResult<Vec<EntryHashB64>> {
+    let history = _get_document_history(hash)?;
+    let live: Vec<EntryHashB64> = history.revisions.into_iter()
+        .filter(|r| r.updated_by.is_empty() && !r.deleted)
+        .map(|r| r.hash)
+        .collect();
+    let mut units: Vec<EntryHash> = vec![];
+    for revision in live.iter() {
+        let unit_hash = get_document_entry(revision.clone().into())?.unit_hash;
+        if !units.contains(&unit_hash) {
+            units.push(unit_hash);
+        }
+    }
+    let mut linked: Vec<AnyLinkableHash> = vec![];
+    for unit_hash in units {
+        linked.extend(get_links(unit_hash, LinkTypes::Head, None)?.into_iter().map(|l| l.target));
+    }
+    if linked.is_empty() {
+        return Ok(live);
+    }
+    Ok(live.into_iter()
+        .filter(|r| linked.contains(&EntryHash::from(r.clone()).into()))
+        .collect())
+}
+
+/// returns the revisions in a document's history that are heads of their unit, more
+/// than one means the document has forked
+#[hdk_extern]
+pub fn get_document_heads(hash: EntryHash) -> ExternResult<Vec<EntryHashB64>> {
+    Ok(_get_document_heads(hash)?)
+}
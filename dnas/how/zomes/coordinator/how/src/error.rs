@@ -21,6 +21,26 @@ pub enum HowError {
     DocumentNotFound,
     #[error("error converting hash")]
     HashConversionError,
+    #[error("Too many redirects while resolving path {0}")]
+    TooManyRedirects(String),
+    #[error("The merge base is not an ancestor of both revisions")]
+    InvalidMergeBase,
+    #[error("Unit has more than one current document")]
+    DocumentConflict,
+    #[error("Update rejected, the document has been updated since the expected head")]
+    StaleHead,
+    #[error("Invalid parent path '{path}': {reason}")]
+    InvalidParent { path: String, reason: String },
+    #[error("No unit at path {0}")]
+    UnitNotFound(String),
+    #[error("Bundle version {0} is newer than this version of How can import")]
+    UnsupportedBundleVersion(u32),
+    #[error("Invalid initialization of {item}: {reason}")]
+    InvalidInitialization { item: String, reason: String },
+    #[error("No preset named {0}")]
+    UnknownPreset(String),
+    #[error("Invalid content in section '{section}': {reason}")]
+    InvalidContent { section: String, reason: String },
 }
 
 pub type HowResult<T> = Result<T, HowError>;
@@ -0,0 +1,347 @@
+use std::collections::{BTreeSet, HashMap};
+
+pub use hdk::prelude::*;
+use hdk::prelude::holo_hash::AgentPubKeyB64;
+use holo_hash::EntryHashB64;
+use how_integrity::{Document, EntryTypes, LinkTypes, Unit};
+
+use crate::comment::CommentPlacement;
+use crate::document::*;
+use crate::error::*;
+use crate::tree::{get_entry_hashes, tree_path, tree_path_to_str, UnitInfo};
+use crate::unit::create_unit_inner;
+use crate::utils::create_link_relaxed;
+use crate::{InitializationReport, InitializedItem, SkipReason};
+
+/// version of the bundle written by export_tree, bundles from later versions are refused
+pub const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedUnit {
+    pub hash: EntryHashB64,
+    pub state: String,
+    pub unit: Unit,
+    pub author: AgentPubKeyB64,
+    pub timestamp: Timestamp,
+}
+
+/// one revision of a document, revisions that updated more than one earlier revision
+/// (merges) list all of them
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedDocument {
+    pub hash: EntryHashB64,
+    pub paths: Vec<String>,
+    pub document: Document,
+    pub previous: Vec<EntryHashB64>,
+    pub deleted: bool,
+    pub author: AgentPubKeyB64,
+    pub timestamp: Timestamp,
+    pub marks: Vec<ExportedMark>,
+}
+
+/// a mark with who originally made it when, recorded as the provenance of the mark when
+/// it's imported, the importing agent becomes its author and only they count as such
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedMark {
+    pub mark_type: u8,
+    pub mark: String,
+    pub author: AgentPubKeyB64,
+    pub timestamp: Timestamp,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedCommentLink {
+    pub base: EntryHashB64,
+    pub comment: EntryHashB64,
+    pub reply: bool,
+    pub placement: Option<CommentPlacement>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeBundle {
+    pub version: u32,
+    pub root: String,
+    pub units: Vec<ExportedUnit>,
+    pub documents: Vec<ExportedDocument>, // oldest first
+    pub comment_links: Vec<ExportedCommentLink>,
+}
+
+/// Who originally wrote an imported entry or mark and when, kept in the tag of a Provenance
+/// link from the entry (or the action of the mark's link) to itself as they are re-authored
+/// by whoever imports them.
+#[derive(Clone, Serialize, Deserialize, Debug, SerializedBytes)]
+#[serde(rename_all = "camelCase")]
+pub struct Provenance {
+    pub author: AgentPubKeyB64,
+    pub timestamp: Timestamp,
+}
+
+// every path at or below path with what is linked there
+fn walk_paths(path: Path, found: &mut Vec<(String, Vec<UnitInfo>, Vec<EntryHash>)>) -> ExternResult<()> {
+    let (units, documents) = get_entry_hashes(&path)?;
+    found.push((tree_path_to_str(path.clone())?, units, documents));
+    for child in path.into_typed(ScopedLinkType::try_from(LinkTypes::Tree)?).children_paths()? {
+        walk_paths(child.path, found)?;
+    }
+    Ok(())
+}
+
+fn export_document(output: DocumentOutput, path: String) -> HowResult<ExportedDocument> {
+    let mut actions = output.actions.clone();
+    actions.sort_by_key(|a| a.as_content().timestamp());
+    let first = actions.first().ok_or(HowError::DocumentNotFound)?.as_content().clone();
+    let hash = output.hash.clone();
+    let mut previous = vec![];
+    for action in actions.iter() {
+        if let Action::Update(update) = action.as_content() {
+            let original: EntryHashB64 = update.original_entry_address.clone().into();
+            if !previous.contains(&original) {
+                previous.push(original);
+            }
+        }
+    }
+    Ok(ExportedDocument {
+        hash,
+        paths: vec![path],
+        document: output.content,
+        previous,
+        deleted: !output.deleted_by.is_empty(),
+        author: first.author().clone().into(),
+        timestamp: first.timestamp(),
+        marks: get_mark_details(EntryHash::from(output.hash))?
+            .into_iter()
+            .map(|m| {
+                // a mark that was imported before keeps its original origin in the bundle
+                let (author, timestamp) = match m.provenance {
+                    Some(provenance) => (provenance.author, provenance.timestamp),
+                    None => (m.author, m.timestamp),
+                };
+                ExportedMark { mark_type: m.mark_type, mark: m.mark, author, timestamp }
+            })
+            .collect(),
+    })
+}
+
+fn export_comment_links(base: &EntryHashB64, link_type: LinkTypes, reply: bool) -> HowResult<Vec<ExportedCommentLink>> {
+    let mut exported = vec![];
+    for link in get_links(EntryHash::from(base.clone()), link_type, None)? {
+        let comment = EntryHash::try_from(link.target.clone()).map_err(|_| HowError::HashConversionError)?;
+        exported.push(ExportedCommentLink {
+            base: base.clone(),
+            comment: comment.into(),
+            reply,
+            placement: CommentPlacement::from_tag(link.tag),
+        });
+    }
+    Ok(exported)
+}
+
+/// exports everything at and below root: the units with their states, every revision of
+/// every document including comments, the marks on them and who wrote them when
+#[hdk_extern]
+pub fn export_tree(root: String) -> ExternResult<TreeBundle> {
+    let mut found = vec![];
+    walk_paths(tree_path(root.clone()), &mut found)?;
+
+    let mut units: Vec<ExportedUnit> = vec![];
+    let mut documents: Vec<ExportedDocument> = vec![];
+    for (path, unit_infos, document_hashes) in found {
+        for info in unit_infos {
+            let hash: EntryHashB64 = info.hash.clone().into();
+            if units.iter().any(|u| u.hash == hash) {
+                continue;
+            }
+            let record = get(info.hash.clone(), GetOptions::default())?
+                .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Unit not found"))))?;
+            let unit: Unit = record
+                .entry()
+                .to_app_option().map_err(|err| wasm_error!(err))?
+                .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Malformed unit"))))?;
+            units.push(ExportedUnit {
+                hash,
+                state: info.state,
+                unit,
+                author: record.action().author().clone().into(),
+                timestamp: record.action().timestamp(),
+            });
+        }
+        let get_input = document_hashes.into_iter()
+            .map(|h| GetInput::new(h.into(), GetOptions::default()))
+            .collect();
+        for output in _get_docs(get_input)? {
+            match documents.iter_mut().find(|d| d.hash == output.hash) {
+                Some(exported) => {
+                    if !exported.paths.contains(&path) {
+                        exported.paths.push(path.clone());
+                    }
+                }
+                None => documents.push(export_document(output, path.clone())?),
+            }
+        }
+    }
+    documents.sort_by_key(|d| d.timestamp);
+
+    let mut comment_links = vec![];
+    for document in documents.iter() {
+        comment_links.extend(export_comment_links(&document.hash, LinkTypes::Comment, false)?);
+        comment_links.extend(export_comment_links(&document.hash, LinkTypes::Reply, true)?);
+    }
+    Ok(TreeBundle {
+        version: BUNDLE_VERSION,
+        root,
+        units,
+        documents,
+        comment_links,
+    })
+}
+
+fn record_provenance(hash: AnyLinkableHash, author: &AgentPubKeyB64, timestamp: Timestamp) -> ExternResult<()> {
+    let provenance = Provenance { author: author.clone(), timestamp };
+    let bytes = SerializedBytes::try_from(provenance).map_err(|e| wasm_error!(e))?;
+    create_link(hash.clone(), hash, LinkTypes::Provenance, LinkTag::new(bytes.bytes().clone()))?;
+    Ok(())
+}
+
+/// checks a bundle before anything is imported, its units' parents have to be in the
+/// bundle, among the paths initialize creates or already in the tree
+pub fn check_bundle(bundle: &TreeBundle, paths: &BTreeSet<String>) -> ExternResult<()> {
+    if bundle.version > BUNDLE_VERSION {
+        return Err(HowError::UnsupportedBundleVersion(bundle.version).into());
+    }
+    let mut bundled = BTreeSet::new();
+    for exported in bundle.units.iter() {
+        bundled.insert(exported.unit.path_str()?);
+    }
+    for exported in bundle.units.iter() {
+        for parent in exported.unit.parents.iter() {
+            if !bundled.contains(parent) && !paths.contains(parent) && get_entry_hashes(&tree_path(parent.clone()))?.0.is_empty() {
+                return Err(HowError::InvalidInitialization {
+                    item: exported.unit.path_str()?,
+                    reason: format!("no unit at parent path {}", parent),
+                }.into());
+            }
+        }
+    }
+    let unit_hashes: Vec<&EntryHashB64> = bundle.units.iter().map(|u| &u.hash).collect();
+    for exported in bundle.documents.iter() {
+        let unit_hash: EntryHashB64 = exported.document.unit_hash.clone().into();
+        if !unit_hashes.contains(&&unit_hash) && get(exported.document.unit_hash.clone(), GetOptions::default())?.is_none() {
+            return Err(HowError::InvalidInitialization {
+                item: exported.paths.first().cloned().unwrap_or_default(),
+                reason: String::from("the document's unit is neither in the bundle nor in the tree"),
+            }.into());
+        }
+    }
+    Ok(())
+}
+
+/// Recreates an exported tree.  Entries are content addressed so they keep their hashes,
+/// and with them all the references between units, documents and comments.  Authorship
+/// can't be carried over, the importing agent authors everything and the original author
+/// and time are recorded as provenance.  Anything already in the tree is skipped so a
+/// bundle can be imported again.
+pub fn import_bundle(bundle: TreeBundle, report: &mut InitializationReport) -> ExternResult<()> {
+    for exported in bundle.units.iter() {
+        let path = exported.unit.path_str()?;
+        let hash = EntryHash::from(exported.hash.clone());
+        if get_entry_hashes(&tree_path(path.clone()))?.0.iter().any(|info| info.hash == hash) {
+            report.skip("unit", &path, Some(exported.hash.clone()), SkipReason::UnitExists);
+            continue;
+        }
+        let output = create_unit_inner(exported.unit.clone(), &exported.state)?;
+        record_provenance(output.info.hash.into(), &exported.author, exported.timestamp)?;
+        report.created_units.push(InitializedItem { path, hash: exported.hash.clone() });
+    }
+
+    // documents are oldest first so the revisions they update already exist
+    let mut actions: HashMap<EntryHashB64, ActionHash> = HashMap::new();
+    let mut created: Vec<&ExportedDocument> = vec![];
+    for exported in bundle.documents.iter() {
+        let path = exported.paths.first().cloned().unwrap_or_default();
+        let hash = EntryHash::from(exported.hash.clone());
+        if let Some(record) = get(hash.clone(), GetOptions::default())? {
+            actions.insert(exported.hash.clone(), record.action_address().clone());
+            report.skip("document", &path, Some(exported.hash.clone()), SkipReason::EntryExists);
+            continue;
+        }
+        let mut action_hash = None;
+        for previous in exported.previous.iter() {
+            if let Some(original) = actions.get(previous) {
+                let hash = update_entry(original.clone(), &exported.document)?;
+                action_hash.get_or_insert(hash);
+            }
+        }
+        let action_hash = match action_hash {
+            Some(hash) => hash,
+            None => create_entry(EntryTypes::Document(exported.document.clone()))?,
+        };
+        actions.insert(exported.hash.clone(), action_hash);
+        for path in exported.paths.iter() {
+            link_document(hash.clone(), path.clone(), &exported.document.document_type)?;
+        }
+        record_provenance(hash.clone().into(), &exported.author, exported.timestamp)?;
+        for mark in exported.marks.iter() {
+            let mut tag_bytes = LinkTag::new(mark.mark.clone()).into_inner();
+            tag_bytes.push(mark.mark_type);
+            let link = create_link_relaxed(hash.clone(), hash.clone(), LinkTypes::Mark, LinkTag::from(tag_bytes))?;
+            record_provenance(link.into(), &mark.author, mark.timestamp)?;
+        }
+        report.created_documents.push(InitializedItem { path, hash: exported.hash.clone() });
+        created.push(exported);
+    }
+
+    // the heads are the live revisions nothing else updated, replacing the heads they updated
+    for exported in created.iter() {
+        let updated = bundle.documents.iter().any(|d| d.previous.contains(&exported.hash));
+        if !updated && !exported.deleted && exported.document.document_type == DOC_DOCUMENT {
+            for previous in exported.previous.iter() {
+                delete_head_links(exported.document.unit_hash.clone(), &EntryHash::from(previous.clone()))?;
+            }
+            create_link(exported.document.unit_hash.clone(), EntryHash::from(exported.hash.clone()), LinkTypes::Head, ())?;
+        }
+    }
+    for exported in created.iter().filter(|d| d.deleted) {
+        if let Some(action_hash) = actions.get(&exported.hash) {
+            delete_entry(action_hash.clone())?;
+        }
+    }
+
+    for link in bundle.comment_links.iter() {
+        let link_type = if link.reply { LinkTypes::Reply } else { LinkTypes::Comment };
+        let base = EntryHash::from(link.base.clone());
+        let comment: AnyLinkableHash = EntryHash::from(link.comment.clone()).into();
+        if get_links(base.clone(), link_type, None)?.iter().any(|l| l.target == comment) {
+            report.skip("commentLink", "", Some(link.comment.clone()), SkipReason::EntryExists);
+            continue;
+        }
+        let tag = match &link.placement {
+            Some(placement) => placement.to_tag()?,
+            None => LinkTag::new(vec![]),
+        };
+        create_link(base, comment, link_type, tag)?;
+        report.comment_links += 1;
+    }
+    Ok(())
+}
+
+// the provenance recorded on hash, only if it was recorded by author, who re-authored it,
+// so that nobody else can claim a different origin for an entry or mark
+pub fn provenance_by(hash: AnyLinkableHash, author: Option<&AgentPubKey>) -> ExternResult<Option<Provenance>> {
+    let link = get_links(hash, LinkTypes::Provenance, None)?
+        .into_iter()
+        .find(|l| author.map_or(true, |author| &l.author == author));
+    Ok(link.and_then(|l| {
+        Provenance::try_from(SerializedBytes::from(UnsafeBytes::from(l.tag.into_inner()))).ok()
+    }))
+}
+
+/// the original author and time of an imported unit or document, none if it wasn't imported
+#[hdk_extern]
+pub fn get_provenance(hash: EntryHash) -> ExternResult<Option<Provenance>> {
+    provenance_by(hash.into(), None)
+}
@@ -8,13 +8,28 @@ pub mod error;
 pub mod unit;
 pub mod document;
 pub mod tree;
+pub mod diagram;
+pub mod diff;
+pub mod merge;
+pub mod comment;
+pub mod template;
+pub mod collection;
+pub mod requirement;
+pub mod content;
+pub mod markdown;
+pub mod render;
+pub mod export;
+pub mod presets;
 pub mod signals;
 pub mod utils;
 
-use hdk::prelude::holo_hash::AgentPubKeyB64;
-use how_integrity::{Unit, Section, Document};
-use unit::create_unit_inner;
-use crate::document::{DocumentInput, create_document};
+use hdk::prelude::holo_hash::{AgentPubKeyB64, EntryHashB64};
+use how_integrity::{Unit, Section, Document, DocumentMeta};
+use unit::{create_unit_inner, get_unit_entry};
+use crate::content::normalize_pending_document;
+use crate::document::{DocumentInput, create_document, _get_docs, DOC_DOCUMENT};
+use crate::export::{TreeBundle, check_bundle, import_bundle};
+use crate::tree::{UnitInfo, get_entry_hashes, tree_path};
 
 #[hdk_extern]
 fn init(_: ()) -> ExternResult<InitCallbackResult> {
@@ -43,47 +58,188 @@ pub struct DocumentInitializer {
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Initialization {
+    #[serde(default)]
     pub units: Vec<(String, Unit)>,
+    #[serde(default)]
     pub documents: Vec<DocumentInitializer>,
+    #[serde(default)]
+    pub bundle: Option<TreeBundle>, // a tree exported from another instance with export_tree
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializedItem {
+    pub path: String,
+    pub hash: EntryHashB64,
+}
+
+/// why initialize left something in the payload alone
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", content = "content")]
+pub enum SkipReason {
+    UnitExists,                     // the same unit is already at the path
+    OtherUnitAtPath(EntryHashB64),  // a different unit is already at the path and is kept
+    DocumentExists,                 // the unit already has a document of this type
+    EntryExists,                    // a bundled revision or comment link that is already there
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedItem {
+    pub kind: String, // "unit", "document" or "commentLink"
+    pub path: String,
+    pub hash: Option<EntryHashB64>,
+    pub reason: SkipReason,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializationReport {
+    pub created_units: Vec<InitializedItem>,
+    pub created_documents: Vec<InitializedItem>,
+    pub comment_links: usize,
+    pub skipped: Vec<SkippedItem>,
+}
+
+impl InitializationReport {
+    pub fn skip(&mut self, kind: &str, path: &str, hash: Option<EntryHashB64>, reason: SkipReason) {
+        self.skipped.push(SkippedItem { kind: String::from(kind), path: String::from(path), hash, reason });
+    }
+}
+
+fn invalid(item: &str, reason: String) -> HowError {
+    HowError::InvalidInitialization { item: String::from(item), reason }
+}
+
+// the units already in the tree at path
+fn existing_units(path: &str) -> ExternResult<Vec<UnitInfo>> {
+    Ok(get_entry_hashes(&tree_path(String::from(path)))?.0)
+}
+
+// checks the whole payload against itself and the tree so nothing is written if any of it is bad
+fn check_initialization(input: &Initialization) -> ExternResult<()> {
+    let mut paths: BTreeSet<String> = BTreeSet::new();
+    let mut payload_units: BTreeMap<String, &Unit> = BTreeMap::new();
+    let known = |path: &str, paths: &BTreeSet<String>| -> ExternResult<bool> {
+        Ok(paths.contains(path) || !existing_units(path)?.is_empty())
+    };
+    for (_, unit) in input.units.iter() {
+        let path = unit.path_str()?;
+        if paths.contains(&path) {
+            return Err(invalid(&path, String::from("more than one unit at this path")).into());
+        }
+        if let Some(parent) = unit.cyclic_parent() {
+            return Err(invalid(&path, format!("the unit would be its own ancestor through {}", parent)).into());
+        }
+        // parents have to come before their children
+        for parent in unit.parents.iter() {
+            if !known(parent, &paths)? {
+                return Err(invalid(&path, format!("no unit at parent path {}", parent)).into());
+            }
+        }
+        paths.insert(path.clone());
+        payload_units.insert(path, unit);
+    }
+    // schemas can come from process documents in the payload
+    let pending: Vec<(&str, &[Section])> = input.documents.iter()
+        .filter(|doc| doc.document_type == DOC_DOCUMENT)
+        .map(|doc| (doc.path.as_str(), doc.content.as_slice()))
+        .collect();
+    for doc in input.documents.iter() {
+        // the unit initialize will use: one already in the tree wins over the payload's
+        let (unit_hash, unit) = match existing_units(&doc.path)?.into_iter().next() {
+            Some(info) => (info.hash.clone(), get_unit_entry(info.hash)?),
+            None => match payload_units.get(&doc.path) {
+                Some(unit) => (hash_entry(*unit)?, (*unit).clone()),
+                None => return Err(invalid(&doc.path, String::from("no unit for the document")).into()),
+            },
+        };
+        let mut document = Document {
+            unit_hash,
+            document_type: doc.document_type.clone(),
+            state: String::new(),
+            editors: doc.editors.clone(),
+            content: doc.content.clone(),
+            meta: DocumentMeta::new(),
+        };
+        normalize_pending_document(&mut document, &unit, &pending).map_err(|err| match err {
+            HowError::InvalidContent { section, reason } => invalid(&doc.path, format!("section {}: {}", section, reason)),
+            err => err,
+        })?;
+    }
+    if let Some(bundle) = &input.bundle {
+        check_bundle(bundle, &paths)?;
+    }
+    Ok(())
 }
 
 #[hdk_extern]
-fn initialize(input: Initialization) -> ExternResult<()> {
-    let mut units: BTreeMap<String, (EntryHash, String)> = BTreeMap::new();
+pub fn initialize(input: Initialization) -> ExternResult<InitializationReport> {
     // add progenitor check for call
+    check_initialization(&input)?;
+    let mut report = InitializationReport::default();
+
+    // running initialize again leaves what is already there alone
+    let mut units: BTreeMap<String, (EntryHash, String)> = BTreeMap::new();
     for (state, unit) in input.units {
         let path = unit.path_str()?;
-        let unit_output = create_unit_inner(unit, &state)?;
-        units.insert(path, (unit_output.info.hash, state));
+        let hash = hash_entry(&unit)?;
+        let existing = existing_units(&path)?;
+        if let Some(info) = existing.iter().find(|info| info.hash == hash) {
+            report.skip("unit", &path, Some(hash.clone().into()), SkipReason::UnitExists);
+            units.insert(path, (hash, info.state.clone()));
+        } else if let Some(info) = existing.first() {
+            report.skip("unit", &path, Some(hash.into()), SkipReason::OtherUnitAtPath(info.hash.clone().into()));
+            units.insert(path, (info.hash.clone(), info.state.clone()));
+        } else {
+            let unit_output = create_unit_inner(unit, &state)?;
+            report.created_units.push(InitializedItem { path: path.clone(), hash: unit_output.info.hash.clone().into() });
+            units.insert(path, (unit_output.info.hash, state));
+        }
     }
     for doc in input.documents {
-        if let Some((unit_hash, state)) = units.get(&doc.path) {
-            let mut sections = Vec::new();
-            for mut section in doc.content {
-                // if we can't find a source path, then assume that the section was manually entered
-                // its source is the document's unit
-                if let Some((unit, _)) = units.get(&section.source_path) {
-                    section.source_unit = Some(unit.clone());
-                } else {
-                    section.source_unit = Some(unit_hash.clone());
-                }
-                sections.push(section);
+        let (unit_hash, state) = match units.get(&doc.path) {
+            Some(unit) => unit.clone(),
+            None => {
+                // checked above, so the unit was already in the tree
+                let info = existing_units(&doc.path)?.into_iter().next().ok_or(HowError::UnitNotFound(doc.path.clone()))?;
+                (info.hash, info.state)
             }
-            let input = DocumentInput {
-                path: doc.path.clone(),
-                document: Document {
-                    unit_hash: unit_hash.clone(),
-                    document_type: doc.document_type,
-                    state: state.clone(),
-                    editors: doc.editors,
-                    content: sections,
-                    meta: BTreeMap::new(),
-                }
-            };
-            create_document(input)?;
-        } else {
-            debug!("no unit for document {:?}", doc);
+        };
+        let (_, hashes) = get_entry_hashes(&tree_path(doc.path.clone()))?;
+        let get_input = hashes.into_iter().map(|h| GetInput::new(h.into(), GetOptions::default())).collect();
+        if let Some(existing) = _get_docs(get_input)?.into_iter()
+            .find(|d| d.deleted_by.is_empty() && d.content.unit_hash == unit_hash && d.content.document_type == doc.document_type) {
+            report.skip("document", &doc.path, Some(existing.hash), SkipReason::DocumentExists);
+            continue;
         }
+        let mut sections = Vec::new();
+        for mut section in doc.content {
+            // if we can't find a source path, then assume that the section was manually entered
+            // its source is the document's unit
+            if let Some((unit, _)) = units.get(&section.source_path) {
+                section.source_unit = Some(unit.clone());
+            } else {
+                section.source_unit = Some(unit_hash.clone());
+            }
+            sections.push(section);
+        }
+        let input = DocumentInput {
+            path: doc.path.clone(),
+            document: Document {
+                unit_hash,
+                document_type: doc.document_type,
+                state,
+                editors: doc.editors,
+                content: sections,
+                meta: DocumentMeta::new(),
+            }
+        };
+        let hash = create_document(input)?;
+        report.created_documents.push(InitializedItem { path: doc.path, hash });
     }
-    Ok(())
+    if let Some(bundle) = input.bundle {
+        import_bundle(bundle, &mut report)?;
+    }
+    Ok(report)
 }
@@ -0,0 +1,319 @@
+pub use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use how_integrity::{ContentType, Section, CONTENT_TYPE_MARKDOWN};
+
+use crate::content::check_section;
+use crate::document::*;
+use crate::error::*;
+use crate::merge::document_path;
+use crate::template::{append_sections, build_initial_document, sections_from_hierarchy};
+use crate::tree::{get_entry_hashes, resolve_path, tree_path};
+use crate::unit::get_unit_entry;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOptions {
+    #[serde(default)]
+    pub level: Option<usize>, // heading level of sections, by default the shallowest level used
+    #[serde(default)]
+    pub add_unmatched: bool, // add sections for headings that match no section instead of skipping them
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportMarkdownInput {
+    pub path: String,
+    pub markdown: String,
+    #[serde(default)]
+    pub options: ImportOptions,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UnmatchedHeading {
+    pub heading: String,
+    pub line: usize,
+    pub added: bool,
+    pub incompatible: Option<String>, // why the text doesn't fit the section with the heading's name
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub document_hash: EntryHashB64,
+    pub created: bool,
+    pub matched: Vec<String>,
+    pub unmatched: Vec<UnmatchedHeading>,
+    pub preamble_ignored: bool, // there was text before the first section that went nowhere
+}
+
+struct Heading {
+    level: usize,
+    text: String,
+    line: usize,
+}
+
+struct Block {
+    heading: String,
+    line: usize,
+    content: String,
+}
+
+// an ATX heading, i.e. up to three spaces, one to six #s and then a space or the end of the line
+fn parse_heading(line: &str, number: usize) -> Option<Heading> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let level = rest.len() - rest.trim_start_matches('#').len();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let text = &rest[level..];
+    if !text.is_empty() && !text.starts_with(' ') && !text.starts_with('\t') {
+        return None;
+    }
+    let text = text.trim().trim_end_matches('#').trim();
+    Some(Heading { level, text: String::from(text), line: number })
+}
+
+// the headings outside of code fences, numbered from 1 like an editor would
+fn parse_headings(lines: &[&str]) -> Vec<Heading> {
+    let mut headings = vec![];
+    let mut fence: Option<&str> = None;
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        let marker = if trimmed.starts_with("```") { Some("```") } else if trimmed.starts_with("~~~") { Some("~~~") } else { None };
+        match (fence, marker) {
+            (None, Some(marker)) => fence = Some(marker),
+            (Some(open), Some(marker)) if open == marker => fence = None,
+            (None, None) => {
+                if let Some(heading) = parse_heading(line, i + 1) {
+                    headings.push(heading);
+                }
+            }
+            _ => (),
+        }
+    }
+    headings
+}
+
+fn trim_blank_lines(lines: &[&str]) -> String {
+    let start = lines.iter().position(|l| !l.trim().is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|l| !l.trim().is_empty()).map(|e| e + 1).unwrap_or(start);
+    lines[start..end.max(start)].join("\n")
+}
+
+// splits the markdown into the title, any text before the first section, and the sections.
+// A lone heading above all the others is the title rather than a section.
+fn split_markdown(markdown: &str, level: Option<usize>) -> (Option<String>, String, Vec<Block>) {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let headings = parse_headings(&lines);
+    let shallowest = match headings.iter().map(|h| h.level).min() {
+        Some(shallowest) => shallowest,
+        None => return (None, trim_blank_lines(&lines), vec![]),
+    };
+    let mut title = None;
+    let level = match level {
+        Some(level) => level,
+        None => {
+            let at_top = headings.iter().filter(|h| h.level == shallowest).count();
+            if at_top == 1 && headings.len() > 1 && headings[0].level == shallowest {
+                title = Some(headings[0].text.clone());
+                headings.iter().map(|h| h.level).filter(|l| *l > shallowest).min().unwrap_or(shallowest)
+            } else {
+                shallowest
+            }
+        }
+    };
+    let starts: Vec<&Heading> = headings.iter().filter(|h| h.level <= level).collect();
+    let first_line = starts.iter().find(|h| h.level == level).map(|h| h.line).unwrap_or(lines.len() + 1);
+    let preamble_from = headings.first().filter(|h| h.level < level).map(|h| h.line).unwrap_or(0);
+    let preamble = trim_blank_lines(&lines[preamble_from..first_line - 1]);
+
+    let mut blocks = vec![];
+    for (i, heading) in starts.iter().enumerate() {
+        if heading.level != level {
+            continue;
+        }
+        let end = starts.get(i + 1).map(|h| h.line - 1).unwrap_or(lines.len());
+        blocks.push(Block {
+            heading: heading.text.clone(),
+            line: heading.line,
+            content: trim_blank_lines(&lines[heading.line..end]),
+        });
+    }
+    (title, preamble, blocks)
+}
+
+// headings and section names match regardless of case and punctuation
+fn match_key(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn fill_section(section: &mut Section, content: String) {
+    if ContentType::parse(&section.content_type).is_text() {
+        section.content_type = String::from(CONTENT_TYPE_MARKDOWN);
+    }
+    section.content = content;
+}
+
+/// imports a markdown file into the current document of the unit at path, creating the
+/// document if the unit doesn't have one yet.  Each heading becomes the content of the
+/// section with the same name, and the report lists the headings no section matched or
+/// whose text doesn't fit the matching section's content type.
+#[hdk_extern]
+pub fn import_markdown(input: ImportMarkdownInput) -> ExternResult<ImportReport> {
+    let path = resolve_path(input.path)?.path;
+    let (units, _) = get_entry_hashes(&tree_path(path.clone()))?;
+    let unit_hash = units.first().map(|u| u.hash.clone()).ok_or(HowError::UnitNotFound(path.clone()))?;
+    let unit = get_unit_entry(unit_hash.clone())?;
+    let template_path = unit.parents.first().cloned().unwrap_or_default();
+
+    let (mut document, head) = match _get_current_document(unit_hash.clone()) {
+        Ok(CurrentDocument::Head(head)) => {
+            let mut document = head.content.clone();
+            // requirements added since the document was created can be filled in too
+            append_sections(&mut document, sections_from_hierarchy(&template_path, 0, SECTION_TYPE_REQUIREMENT)?);
+            (document, Some(head.hash))
+        }
+        Ok(CurrentDocument::Conflict(_)) => return Err(HowError::DocumentConflict.into()),
+        Err(HowError::DocumentNotFound) => (build_initial_document(unit_hash.clone(), &unit, &template_path)?, None),
+        Err(err) => return Err(err.into()),
+    };
+
+    let (title, preamble, blocks) = split_markdown(&input.markdown, input.options.level);
+    let mut matched = vec![];
+    let mut unmatched = vec![];
+    if let Some(title) = title {
+        if let Some(section) = document.content.iter_mut().find(|s| s.name == "title" && s.section_type == SECTION_TYPE_CONTENT) {
+            section.content = title;
+            matched.push(section.name.clone());
+        }
+    }
+    for block in blocks {
+        let key = match_key(&block.heading);
+        match document.content.iter().position(|s| s.section_type == SECTION_TYPE_CONTENT && match_key(&s.name) == key) {
+            Some(i) => {
+                let mut section = document.content[i].clone();
+                fill_section(&mut section, block.content);
+                // e.g. prose under the heading of a number or date section
+                match check_section(&document, &section) {
+                    Ok(()) => {
+                        matched.push(section.name.clone());
+                        document.content[i] = section;
+                    }
+                    Err(HowError::InvalidContent { reason, .. }) => unmatched.push(UnmatchedHeading {
+                        heading: block.heading,
+                        line: block.line,
+                        added: false,
+                        incompatible: Some(reason),
+                    }),
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            None => {
+                if input.options.add_unmatched {
+                    document.content.push(Section::new(&block.heading, SECTION_TYPE_CONTENT, CONTENT_TYPE_MARKDOWN, SOURCE_MANUAL, None, &block.content));
+                }
+                unmatched.push(UnmatchedHeading {
+                    heading: block.heading,
+                    line: block.line,
+                    added: input.options.add_unmatched,
+                    incompatible: None,
+                });
+            }
+        }
+    }
+
+    let created = head.is_none();
+    let document_hash = match head {
+        Some(hash) => update_document(UpdateDocumentInput {
+            hash: hash.clone(),
+            path: document_path(&document)?,
+            document,
+            expected_head: Some(hash),
+        })?,
+        None => create_document(DocumentInput { path: unit.path_str()?, document })?,
+    };
+    Ok(ImportReport {
+        document_hash,
+        created,
+        matched,
+        unmatched,
+        preamble_ignored: !preamble.is_empty(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks(blocks: &[Block]) -> Vec<(&str, usize, &str)> {
+        blocks.iter().map(|b| (b.heading.as_str(), b.line, b.content.as_str())).collect()
+    }
+
+    #[test]
+    fn parses_atx_headings() {
+        let heading = parse_heading("  ## Scope ##", 3).unwrap();
+        assert_eq!((heading.level, heading.text.as_str(), heading.line), (2, "Scope", 3));
+        assert_eq!(parse_heading("#", 1).map(|h| h.text), Some(String::from("")));
+        assert!(parse_heading("#hashtag", 1).is_none());
+        assert!(parse_heading("    # indented code", 1).is_none());
+        assert!(parse_heading("####### too deep", 1).is_none());
+        assert!(parse_heading("plain text", 1).is_none());
+    }
+
+    #[test]
+    fn headings_in_code_fences_are_ignored() {
+        let lines = vec!["# A", "```", "# not a heading", "~~~", "```", "## B", "~~~", "# nor this", "~~~"];
+        let headings: Vec<(usize, String, usize)> = parse_headings(&lines).into_iter().map(|h| (h.level, h.text, h.line)).collect();
+        assert_eq!(headings, vec![(1, String::from("A"), 1), (2, String::from("B"), 6)]);
+    }
+
+    #[test]
+    fn a_lone_top_heading_is_the_title() {
+        let markdown = "# Doc\n\nintro\n\n## Summary\n\nthe summary\n\n## Scope\nall\n";
+        let (title, preamble, sections) = split_markdown(markdown, None);
+        assert_eq!(title, Some(String::from("Doc")));
+        assert_eq!(preamble, "intro");
+        assert_eq!(blocks(&sections), vec![("Summary", 5, "the summary"), ("Scope", 9, "all")]);
+    }
+
+    #[test]
+    fn sections_are_split_at_the_shallowest_level() {
+        let markdown = "intro\n## S\ntext\n### Sub\nmore\n## T\nt";
+        let (title, preamble, sections) = split_markdown(markdown, None);
+        assert_eq!(title, None);
+        assert_eq!(preamble, "intro");
+        // deeper headings stay in the text of their section
+        assert_eq!(blocks(&sections), vec![("S", 2, "text\n### Sub\nmore"), ("T", 6, "t")]);
+    }
+
+    #[test]
+    fn the_level_can_be_chosen() {
+        let markdown = "# A\n## a1\ntext\n## a2\n# B\nb";
+        let (title, preamble, sections) = split_markdown(markdown, Some(2));
+        assert_eq!(title, None);
+        assert_eq!(preamble, "");
+        assert_eq!(blocks(&sections), vec![("a1", 2, "text"), ("a2", 4, "")]);
+    }
+
+    #[test]
+    fn markdown_without_headings_is_all_preamble() {
+        let (title, preamble, sections) = split_markdown("\njust text\n\n", None);
+        assert_eq!((title, preamble.as_str(), sections.len()), (None, "just text", 0));
+    }
+
+    #[test]
+    fn headings_match_names_regardless_of_case_and_punctuation() {
+        assert_eq!(match_key("Scope & Purpose"), match_key("scope purpose"));
+        assert_eq!(match_key("  Next-Steps:"), "next steps");
+        assert_ne!(match_key("next steps"), match_key("nextsteps"));
+    }
+}
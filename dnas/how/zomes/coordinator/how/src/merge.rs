@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+pub use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use how_integrity::{ContentType, Document, DocumentMeta, Section, Unit, CONTENT_TYPE_CONFLICT};
+
+use crate::document::*;
+use crate::error::*;
+use crate::tree::resolve_path;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeDocumentsInput {
+    pub base: EntryHash,
+    pub left: EntryHash,
+    pub right: EntryHash,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeOutput {
+    pub hash: EntryHashB64,
+    pub conflicts: Vec<String>, // names of sections that were changed on both sides
+}
+
+fn same_section(a: Option<&Section>, b: Option<&Section>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => a.content == b.content && a.content_type == b.content_type && a.section_type == b.section_type,
+        _ => false,
+    }
+}
+
+// both variants of a conflicting section are kept in its content between markers
+// so that an editor can resolve them by hand
+fn conflict_section(left: Option<&Section>, right: Option<&Section>) -> Section {
+    let mut section = left.or(right).cloned().expect("one side of a conflict must have the section");
+    // markers would make structured content invalid, so such a section is marked as a
+    // conflict of its type until it is resolved
+    if !ContentType::parse(&section.content_type).is_text() {
+        section.content_type = format!("{}{}", CONTENT_TYPE_CONFLICT, section.content_type);
+    }
+    let left_content = left.map(|s| s.content.as_str()).unwrap_or("");
+    let right_content = right.map(|s| s.content.as_str()).unwrap_or("");
+    section.content = format!("<<<<<<< left\n{}\n=======\n{}\n>>>>>>> right", left_content, right_content);
+    section
+}
+
+fn find_section<'a>(doc: &'a Document, name: &str) -> Option<&'a Section> {
+    doc.content.iter().find(|s| s.name == name)
+}
+
+// three way merge of a whole section: a section changed on only one side takes that
+// side's version, a section changed on both sides differently is a conflict
+pub fn merge_section(base: Option<&Section>, left: Option<&Section>, right: Option<&Section>) -> (Option<Section>, bool) {
+    if same_section(left, right) || same_section(right, base) {
+        (left.cloned(), false)
+    } else if same_section(left, base) {
+        (right.cloned(), false)
+    } else {
+        (Some(conflict_section(left, right)), true)
+    }
+}
+
+fn merge_sections(base: &Document, left: &Document, right: &Document) -> (Vec<Section>, Vec<String>) {
+    let mut names: Vec<String> = left.content.iter().map(|s| s.name.clone()).collect();
+    for s in right.content.iter().chain(base.content.iter()) {
+        if !names.contains(&s.name) {
+            names.push(s.name.clone());
+        }
+    }
+    let mut sections = vec![];
+    let mut conflicts = vec![];
+    for name in names {
+        let (merged, conflict) = merge_section(find_section(base, &name), find_section(left, &name), find_section(right, &name));
+        if conflict {
+            conflicts.push(name.clone());
+        }
+        if let Some(section) = merged {
+            sections.push(section);
+        }
+    }
+    (sections, conflicts)
+}
+
+// merged key by key on the stored form of the meta data, so extension keys merge too
+fn merge_meta(base: &DocumentMeta, left: &DocumentMeta, right: &DocumentMeta) -> DocumentMeta {
+    let base: BTreeMap<String, String> = base.clone().into();
+    let left: BTreeMap<String, String> = left.clone().into();
+    let right: BTreeMap<String, String> = right.clone().into();
+    let mut meta = left.clone();
+    for (key, value) in right.iter() {
+        // right only wins where left didn't change the value
+        if left.get(key) == base.get(key) {
+            meta.insert(key.clone(), value.clone());
+        }
+    }
+    for key in base.keys() {
+        if !right.contains_key(key) && left.get(key) == base.get(key) {
+            meta.remove(key);
+        }
+    }
+    DocumentMeta::from(meta)
+}
+
+pub fn document_record(hash: EntryHash) -> ExternResult<(Record, Document)> {
+    let record = get(hash, GetOptions::default())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Document not found"))))?;
+    let document: Document = record
+        .entry()
+        .to_app_option().map_err(|err| wasm_error!(err))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Malformed document"))))?;
+    Ok((record, document))
+}
+
+pub fn document_path(document: &Document) -> ExternResult<String> {
+    let record = get(document.unit_hash.clone(), GetOptions::default())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Unit not found"))))?;
+    let unit: Unit = record
+        .entry()
+        .to_app_option().map_err(|err| wasm_error!(err))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Malformed unit"))))?;
+    Ok(resolve_path(unit.path_str()?)?.path)
+}
+
+/// merges two heads that were both updated from base, the merge revision updates
+/// both heads so that the history converges again
+#[hdk_extern]
+pub fn merge_documents(input: MergeDocumentsInput) -> ExternResult<MergeOutput> {
+    let (_, base) = document_record(input.base.clone())?;
+    let (left_record, left) = document_record(input.left.clone())?;
+    let (right_record, right) = document_record(input.right.clone())?;
+    let heads = _get_document_heads(input.left.clone())?;
+    if !heads.contains(&input.left.clone().into()) || !heads.contains(&input.right.clone().into()) {
+        return Err(HowError::StaleHead.into());
+    }
+    if !is_revision_ancestor(&input.base, input.left.clone())? || !is_revision_ancestor(&input.base, input.right.clone())? {
+        return Err(HowError::InvalidMergeBase.into());
+    }
+
+    let (content, conflicts) = merge_sections(&base, &left, &right);
+    let mut editors = left.editors.clone();
+    for editor in right.editors.iter() {
+        if !editors.contains(editor) {
+            editors.push(editor.clone());
+        }
+    }
+    let mut meta = merge_meta(&base.meta, &left.meta, &right.meta);
+    meta.upgrade();
+    meta.merge_conflicts = if !conflicts.is_empty() { Some(conflicts.clone()) } else { None };
+    let merged = Document {
+        unit_hash: left.unit_hash.clone(),
+        document_type: left.document_type.clone(),
+        state: if left.state == base.state { right.state.clone() } else { left.state.clone() },
+        editors,
+        content,
+        meta,
+    };
+
+    let hash = _update_document(left_record.action_address().clone(), document_path(&merged)?, &merged)?;
+    // the same entry also updates the right head, it is already linked so just retire the right head
+    update_entry(right_record.action_address().clone(), &merged)?;
+    delete_head_links(right.unit_hash.clone(), &input.right)?;
+    Ok(MergeOutput {
+        hash,
+        conflicts,
+    })
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+pub use hdk::prelude::*;
+use hdk::prelude::holo_hash::AgentPubKeyB64;
+use how_integrity::{Section, Unit, UnitMeta};
+
+use crate::document::*;
+use crate::error::*;
+use crate::unit::{ALIVE_STATE, BUILD_STATE, START_STATE};
+use crate::{initialize, DocumentInitializer, Initialization, InitializationReport};
+
+mod simple;
+mod holochain;
+
+// Trees that can be bootstrapped with or without the UI, which initializes with them too.
+// They are the trees the UI used to build itself, tests/src/fixtures has those as they
+// were so the tests can check the presets still produce them.
+
+pub struct PresetUnit {
+    pub state: &'static str,
+    pub parent: Option<&'static str>,
+    pub path_abbreviation: &'static str,
+    pub short_name: &'static str,
+}
+
+pub struct PresetSection {
+    pub name: &'static str,
+    pub section_type: &'static str,
+    pub content_type: &'static str,
+    pub source_path: &'static str,
+    pub content: &'static str,
+}
+
+pub struct PresetDocument {
+    pub path: &'static str,
+    pub document_type: &'static str,
+    pub content: &'static [PresetSection],
+}
+
+pub struct Preset {
+    pub name: &'static str,
+    pub process_root: &'static str, // where the define, refine and align processes live
+    pub units: &'static [PresetUnit],
+    pub documents: &'static [PresetDocument],
+}
+
+pub const PRESETS: &[Preset] = &[simple::PRESET, holochain::PRESET];
+
+impl Preset {
+    // every unit starts out with the standard process of each state
+    fn processes(&self) -> Vec<(String, String)> {
+        vec![
+            (format!("{}.define", self.process_root), String::from("declaration")),
+            (format!("{}.refine", self.process_root), String::from("comment_period")),
+            (format!("{}.align", self.process_root), String::from("vote")),
+        ]
+    }
+
+    /// the initialization for the preset with the progenitor as steward and editor of everything
+    pub fn initialization(&self, progenitor: AgentPubKeyB64) -> Initialization {
+        let units = self.units.iter().map(|unit| {
+            (String::from(unit.state), Unit {
+                parents: unit.parent.map(|p| vec![String::from(p)]).unwrap_or_default(),
+                version: String::from("vidx:1"),
+                short_name: String::from(unit.short_name),
+                path_abbreviation: String::from(unit.path_abbreviation),
+                stewards: vec![progenitor.clone()],
+                processes: self.processes(),
+                history: BTreeMap::new(),
+                meta: UnitMeta::default(),
+            })
+        }).collect();
+        let documents = self.documents.iter().map(|doc| DocumentInitializer {
+            path: String::from(doc.path),
+            document_type: String::from(doc.document_type),
+            editors: vec![progenitor.clone()],
+            content: doc.content.iter()
+                .map(|s| Section::new(s.name, s.section_type, s.content_type, s.source_path, None, s.content))
+                .collect(),
+        }).collect();
+        Initialization { units, documents, bundle: None }
+    }
+}
+
+pub fn get_preset(name: &str) -> HowResult<&'static Preset> {
+    PRESETS.iter().find(|p| p.name == name).ok_or(HowError::UnknownPreset(String::from(name)))
+}
+
+#[hdk_extern]
+pub fn list_presets(_: ()) -> ExternResult<Vec<String>> {
+    Ok(PRESETS.iter().map(|p| String::from(p.name)).collect())
+}
+
+/// bootstraps the tree from one of the built in presets with the calling agent as progenitor,
+/// like initialize it can be run again and only adds what is missing
+#[hdk_extern]
+pub fn initialize_with_preset(name: String) -> ExternResult<InitializationReport> {
+    let preset = get_preset(&name)?;
+    let progenitor: AgentPubKeyB64 = agent_info()?.agent_initial_pubkey.into();
+    initialize(preset.initialization(progenitor))
+}
@@ -0,0 +1,885 @@
+// the Holochain standards tree, as the UI's initHolochain.ts built it
+use super::*;
+
+pub const PRESET: Preset = Preset {
+    name: "holochain",
+    process_root: "social.ops.process",
+    units: &[
+        PresetUnit { state: ALIVE_STATE, parent: None, path_abbreviation: "", short_name: "Holochain Standards" },
+        PresetUnit { state: ALIVE_STATE, parent: None, path_abbreviation: "social", short_name: "Social Protocols" },
+        PresetUnit { state: ALIVE_STATE, parent: None, path_abbreviation: "tech", short_name: "Technical" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("tech"), path_abbreviation: "hApps", short_name: "hApp Standards" },
+        PresetUnit { state: START_STATE, parent: Some("tech.hApps"), path_abbreviation: "ERC721_interop", short_name: "ERC721 Interoperability Standard" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social"), path_abbreviation: "ops", short_name: "Operations" },
+        PresetUnit { state: BUILD_STATE, parent: Some("social"), path_abbreviation: "biz", short_name: "Business, Legal, Finance, Regulatory" },
+        PresetUnit { state: BUILD_STATE, parent: Some("social.biz"), path_abbreviation: "exec", short_name: "Executive Function/ Council" },
+        PresetUnit { state: BUILD_STATE, parent: Some("social"), path_abbreviation: "dev_support", short_name: "Developer Engagement & Support" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social.ops"), path_abbreviation: "process", short_name: "How Processes" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social.ops.process"), path_abbreviation: "define", short_name: "Proposal procesess" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social.ops.process.define"), path_abbreviation: "declaration", short_name: "Declaration" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social.ops.process.define"), path_abbreviation: "petition", short_name: "Petition" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social.ops.process"), path_abbreviation: "refine", short_name: "Refinement Processes" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social.ops.process.refine"), path_abbreviation: "comment_period", short_name: "Comment Period" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social.ops.process"), path_abbreviation: "align", short_name: "Unit Processes" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social.ops.process.align"), path_abbreviation: "vote", short_name: "Voting" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social.ops.process.align"), path_abbreviation: "consensus", short_name: "Consensus" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("social.ops.process.align"), path_abbreviation: "sortition", short_name: "Sortition" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("tech"), path_abbreviation: "hc_framework", short_name: "Holochain Framework" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("tech.hc_framework"), path_abbreviation: "runtime", short_name: "Runtime Environments" },
+        PresetUnit { state: BUILD_STATE, parent: Some("tech.hc_framework.runtime"), path_abbreviation: "conductor", short_name: "Holochain Conductor" },
+        PresetUnit { state: BUILD_STATE, parent: Some("tech.hc_framework.runtime.conductor"), path_abbreviation: "api", short_name: "Holochain Conductor API" },
+        PresetUnit { state: BUILD_STATE, parent: Some("tech.hc_framework.runtime.conductor"), path_abbreviation: "services", short_name: "Holochain Conductor Services" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("tech.hc_framework"), path_abbreviation: "organism", short_name: "Network Organism" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("tech.hc_framework.organism"), path_abbreviation: "organ", short_name: "Network Organ" },
+        PresetUnit { state: BUILD_STATE, parent: Some("tech.hc_framework.organism"), path_abbreviation: "ui", short_name: "User Interface" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("tech.hc_framework.organism.organ"), path_abbreviation: "cell", short_name: "Cell State" },
+        PresetUnit { state: BUILD_STATE, parent: Some("tech.hc_framework.organism.organ"), path_abbreviation: "shared_viz", short_name: "Shared Visibility" },
+        PresetUnit { state: BUILD_STATE, parent: Some("tech.hc_framework.organism.organ"), path_abbreviation: "dna", short_name: "DNA" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("tech.hc_framework.organism.organ.cell"), path_abbreviation: "ephemeral", short_name: "Ephemeral State" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("tech.hc_framework.organism.organ.cell"), path_abbreviation: "immutable", short_name: "Immutable State" },
+    ],
+    documents: &[
+        PresetDocument {
+            path: "",
+            document_type: DOC_TREE_META,
+            content: &[
+                PresetSection {
+                    name: "Holochain Community Standards",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"processRoot\":\"social.ops.process\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/plain",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"title of the standard being defined\"}",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/markdown",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"A multi-sentence (short paragraph) description of the purpose towards which alignment is being sought.\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Technical Aligments",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To align on the ontology, specifications, requirements, conventions, protocols, release patterns, testing frameworks, api, documentation etc for Holochain itself, as well as for standards for application types/classes/affordances built using Holochain",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Holochain Framework",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To align on the ontology, specifications, requirements, conventions, protocols, release patterns, testing frameworks, api, documentation etc for the Holochain framework",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hApps",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "hApp Standards",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To align on standards for classes of holochain hApps",
+                },
+                PresetSection {
+                    name: "summary",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/markdown",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"A multi-sentence (short paragraph) technical summary. This should be a very terse and human-readable version of the specification section. Someone should be able to read only the abstract to get the gist of what this specification does.\"}",
+                },
+                PresetSection {
+                    name: "zome signatures",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/plain",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"Machine readable function signatures to expect of zomes that implement this standard.\"}",
+                },
+                PresetSection {
+                    name: "reference implementation",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/plain",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"An optional section that contains a reference/example implementation that people can use to assist in understanding or implementing this specification.\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hApps.ERC721_interop",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "ERC721 Interoperation Standard",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To align on how Holochain DHTs can be used to hold data for NFTs based on the ERC721 standard.",
+                },
+                PresetSection {
+                    name: "summary",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "tech.hApps",
+                    content: "An agent or agents in a Holochain DHT commit an entry who’s HRL will be used as the referenced in the minted NFT. The minted NFT’s id a hash of the minter plus Holochain entry hash. This id then is used on the Holochain side to create a baseless link to the NFT entry. The validation rules of that link ensure the trustability of any returned value for off-ethereum-chain reference by third parties. This creates a simple, fully-decentralized mechanism to provide a non-oracle based provenance of an NFT’s resource by preventing front-running.",
+                },
+                PresetSection {
+                    name: "context",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "social.ops.process.define",
+                    content: "\n### Story  (notes)\n\nBlockchain lives in a bubble.  There's no reasonable way in blockchain land to reference the state-of-afairs outside in the world without going through a trusted party.  Thus this goes counter to what blockchains are all about \"trust-lessness\".  Holochain DLTs are bound to the \"real-world\" through agency and accountability.  \n\nIn the blockchain world chain-of-custody is a non-fungible token that you watch move from place to place.  In the Holochain world chain-of-custody is by the agents accountable from it along the way signing for it.\n\nThe way we looking at NFTs right now is that the low hanging fruit is to replace IPFS for payloads rather than to try and replace the token spec, which is why we put up a PR to add ethereum compatible hashing algos, which would allow us to align NFT ids with validation logic on the DHT. Why go after the consensus mechanism (generally accepted as working fine, and difficult to do on holochain) rather than the payload hosting (generally accepted as very problematic and easy to do on holochain)?\n\n### Problem/question:\n\nNot require an oracle to secure/validate some form of economic activity, but have the participants able to provide adequate proof on their own. Oracles can be gamed and be incented to lie.\n\nHaving an NFT on the blockchain is not enough in general. Even having an IPFS hash of an NFT (off chain) isn't enough because IPFS has not context for proving validation of the hash.\n\nThe original minter of an NFT is often eligible for royalties, or may have extra admin permissions, etc.\n\nIt is important that as well as the opaque hash in the NFT lining up with the location in the holochain CAS, the minter needs to line up with the minter specified on the holochain side.\n\nSpecifying a minter on holochain might be optional, as the private data already affords security for as long as the private data remains private.\n\nThe issue is that for some use cases there may be economic incentive for a participant to leak the data early:\n\n- Maybe they lost a game and are a sore loser and want to grief the winner\n- Maybe they will get access to royalites or other economic incentives that they shouldn't have, simply participating in a session doesn't necessarily mean you should own the resulting NFT\n\nSo what we want is a way to specify on holochain who can mint, and have the cryptography match on both sides.\n\nThe smart contract logic is probably the limiting/driving factor here as the `msg.sender` in an evm contract is already the address that the signature of the transaction has been validated against. There isn't functionality in the evm to efficiently support other signature schemes because gas etc.\n\nSo somehow holochain has to mimic whatever results in `msg.sender` that has to be in a validatable state for any entries that could inform an NFT mint and also be included in whatever is hashed.\n\n### End Goal\n\nYou can write a regular happ, and then by following this standard you can get NFT id that points to some snapshot of a hApps state (like a game result)\n\nFurthermore we must:\n1. Prevent pre-miniting of something by an attacker, i.e. only the winner of a game can mint, not the first one to try and mint.\n2. Prevent attacker from blocking a mint, i.e. the loser of a game can't prevent the winner from minting.\n3. Prevent non participants from doing either 1 or 2.\n4. Prevent someone on the holochain side pretending to be someone they aren't on the ETH side\n",
+                },
+                PresetSection {
+                    name: "specification",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "social.ops.process.define",
+                    content: "\n### Example\n\n#### Consuming Workflow\n\n0. Axiom: I know the ID and who minted it.\n1. Get URL from NFT\n2. Treat URL as Holochain HRL\n3. Follow HRL to get value from the DHT (using get_links)\n4. If something is returned then NFT is valid\n5. If you believe you might be under eclipse then also run the validation yourself\n\n#### Minting Workflow\n\n0. Assumptions:\n    1. hApp state of NFT value has been committed as an entry (i.e. winning board state) known as the NFT target.\n    2. This target is verifiable to be \"owned\" by an ETH agent via an `ownerAgentOf(EntryHash)->EthAddress` (i.e. either it's content contains the EthAddress of the owner, or the address can be retrieved for that state for example by get_link on the game winners AgentPubKey)\n1. Get target raw hash (32bytes of EntryHash) from a zome call\n2. call `mintNFT()` on ethereum side\n3. get ID of minted NFT from indexer\n4. call `linkNFT(id, target)` on the holochain \n\n\nObvious gaps:\n\n- It's not possible in the HDK to hash non-entry data\n- We don't have baseless links\n- We don't have eth compatibility either in HDK or util crate\n    - Nico work for membrane?\n    - Per-target ETH signing?\n\nHolochain side:\n\n``` rust\n// Validation:\n// - minter == ownerAgentOf(target)\n// - sha256(minter + target) == base  (using raw 32)\n// Note in validation we have to convert arget to raw hash (32byte version) for the hash to match the base\nstruct BaselessLink {\n    base: TheNFTId,\n    target: EntryHash,  \n}\n\nextern function linkNFT(id, target) -> ExternResult<()> {\n    let base = hash(ownerAgentOf(target)+target)?;\n    create_baseless_link(base, target)?;\n    Ok(())\n}\n// from Records\nfn link_nft_to_record(input: LinkingInput) -> ExternResult<HeaderHashB64> {\n    let target: EntryHash = input.target.into();\n    let minter = get_target_minter(target.clone())?;\n    let mut hashable : [u8; 64] = [0; 64];\n    let (one, two) = hashable.split_at_mut(32);    \n    one.copy_from_slice(&minter);\n    two.copy_from_slice(&target.get_raw_32());\n    let raw_base = hash_keccak256(hashable.to_vec())?;\n    if raw_base != input.base {\n        return Err(WasmError::Guest(\"provided nft id doesn't match hash(minter+target)\".into()));\n    }\n    let base = EntryHash::from_raw_32(raw_base);\n    let header = create_link(base, target, ())?;\n    Ok(header.into())\n}\n\n```\n\nEthereum Side:\n\n```solidity=\nfunction mintNFT(uint256 memory target_) external {\n    uint256 id_;\n    // Id is the bytes of the msg.sender and target_\n    // concatenated and hashed.\n    assembly {\n        let mem_ := mload(0x40)\n        mstore(mem_, msg.sender)\n        mstore(add(mem_, 0x20), target_)\n        mstore(0x40, add(mem_, 0x40))\n        id_ := keccak256(mem_, 0x40)\n    }\n    _mint(id_, msg.sender);\n}\n```\n\n",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.runtime",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Runtime Environments",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To provide aligment on standards for Holochain run-time environments",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.runtime.conductor",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Conductor",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "fixme",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.runtime.conductor.services",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Conductor Services",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "fixme",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.runtime.conductor.api",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Conductor API",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "fixme",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.organism",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Network Organism",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To provide aligment on standards for Holochain hApps",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.organism.ui",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "User Interface",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "fixme",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.organism.organ",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Network Organ",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "DNA level standards... fixme",
+                },
+                PresetSection {
+                    name: "host_fn spec",
+                    section_type: SECTION_TYPE_COLLECTION_DEF,
+                    content_type: "text/markdown",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"specification of host functions\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.organism.organ.dna",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "DNA",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "fixme",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.organism.organ.cell",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Cell",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "fixme",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.organism.organ.shared_viz",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Shared Visibility",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "fixme",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.organism.organ.cell.ephemeral",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Ephemeral State",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "fixme",
+                },
+                PresetSection {
+                    name: "host_fn spec",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "tech.hc_framework.organism.organ",
+                    content: "long spec about ephemeral data",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "tech.hc_framework.organism.organ.cell.immutable",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Immutable State",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "fixme",
+                },
+                PresetSection {
+                    name: "host_fn spec",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "tech.hc_framework.organism.organ",
+                    content: "long spec about immutability",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Social Aligments",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To align on all of the human processes and patterns involved in building and governing Holochain and it's ecosystem",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Operations",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To align on the operational aspects of the Holochain open-source community, including change management process of this tool.",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.biz",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Business, Legal, Finance, Regulatory",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To align on how to interface with the world of regulations, banking, and laws. To create visibility and community feedback into significant decision-making happening in these domains.",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.biz.exec",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Executive Function/ Council",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To have surface area for interfacing with the Holochain Foundation's Executive Council, and make visible the people, and domains in which they are empowered and accountable to act.",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.dev_support",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Developer Engagment & Support",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To align on commitments the open-source community has to supporting and engaging with developers in the community",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops.process",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Alignment Processes",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "To specify all the different processes that are used to add new nodes to the tree and come to alignment on them.",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops.process.define",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Definition Process Type",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "This process type is used to define some standard in the tree.   All definitions must have a context, as specification and a rationale.",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/plain:long",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"description of threshold for state to move to refinement\"}",
+                },
+                PresetSection {
+                    name: "context",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "text/plain:long",
+                    source_path: "social.ops.process.define",
+                    content: "{\"description\": \"Description of the context and use-case an why it requires a standard. What problem does it solve? Why should someone want to implement this standard? What benefit does it provide to the Holochain ecosystem? What use cases does this standard address?\"}",
+                },
+                PresetSection {
+                    name: "specification",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "text/markdown",
+                    source_path: "social.ops.process.define",
+                    content: "{\"description\": \"Description of the syntax, semantics, state diagrams, and workflows of any new feature/protocol/process. The specification should be detailed enough to allow for multiple interoperable implementations.\"}",
+                },
+                PresetSection {
+                    name: "rationale",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "text/plain:long",
+                    source_path: "social.ops.process.define",
+                    content: "{\"description\": \"Description of what motivated the design and why particular design decisions were made. It should describe alternate designs that were considered and related work, e.g. how the feature is supported in other languages. The rationale may also provide evidence of consensus within the community, and should discuss important objections or concerns raised during discussion.\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops.process.refine",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Refine Process Type",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "This process type is used to refine a standard in the tree.",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/plain:long",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"description of threshold for state to move to alignment\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops.process.align",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Align Process Type",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "This process type is used to align on a standard after it has been defined and refined.",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/plain:long",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"description of threshold for state to move to alive\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops.process.define.petition",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Definition by petition",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The petition definition variant allways includes a list of the petitioners",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "social.ops.process.define",
+                    content: "A petition can move to refine when the threshold number of people have signed on to the petition.",
+                },
+                PresetSection {
+                    name: "petitioners",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "json/agents",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"who is making the petition\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops.process.define.declaration",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Definition by declaration",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The declaration definition variant allways includes the zome signatures and an optional reference implementation",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "social.ops.process.define",
+                    content: "A declaration can move to refinement whenever the stewards decide to.",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops.process.refine.comment_period",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Comment Period",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The Comment Period refinement variant specifies a timeframe for commenting",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "social.ops.process.refine",
+                    content: "Refinement can complete after the specified number of weeks in the period.",
+                },
+                PresetSection {
+                    name: "commenting",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "control/comments",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"commenting and making change suggestions\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops.process.align.vote",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Votes",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The Votes alignment variant specifies a voting group, and a count of those votes",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "social.ops.process.align",
+                    content: "Voting can move to Alive when the voting criteria have been fulfilled.",
+                },
+                PresetSection {
+                    name: "vote",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "control/voting",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"voting afforadance\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops.process.align.consensus",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Consensus",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The Consensus alignment variant specifies an outcome of consensus decision that a group has taken",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "social.ops.process.align",
+                    content: "Consensus can move to Alive when the consensus outcome has been recorded in the outcome section.",
+                },
+                PresetSection {
+                    name: "outcome",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "text/markdown",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"results of the consensus decision\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "social.ops.process.align.sortition",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Sortition",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The Sortition alignment variant specifies a group of people who will decide on the unit and it's outcome of consensus decision that a group has taken",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "social.ops.process.align",
+                    content: "Sortition can move to Alive when the sortition decision has been recorded in the outcome section by the stewards and the agents in the people section have all marked it as approved.",
+                },
+                PresetSection {
+                    name: "people",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "json/agents",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"list of people who will decide\"}",
+                },
+                PresetSection {
+                    name: "outcome",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "text/markdown",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"results of the sorition decision\"}",
+                },
+            ],
+        },
+    ],
+};
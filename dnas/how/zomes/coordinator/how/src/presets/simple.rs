@@ -0,0 +1,383 @@
+// the social protocols tree, as the UI's initSimple.ts built it
+use super::*;
+
+pub const PRESET: Preset = Preset {
+    name: "simple",
+    process_root: "soc_proto.process",
+    units: &[
+        PresetUnit { state: ALIVE_STATE, parent: None, path_abbreviation: "", short_name: "Alignments" },
+        PresetUnit { state: ALIVE_STATE, parent: None, path_abbreviation: "soc_proto", short_name: "Social Protocols" },
+        PresetUnit { state: ALIVE_STATE, parent: None, path_abbreviation: "projects", short_name: "Projects" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("soc_proto"), path_abbreviation: "process", short_name: "How Processes" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("soc_proto.process"), path_abbreviation: "define", short_name: "Proposal procesess" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("soc_proto.process.define"), path_abbreviation: "declaration", short_name: "Declaration" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("soc_proto.process.define"), path_abbreviation: "petition", short_name: "Petition" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("soc_proto.process"), path_abbreviation: "refine", short_name: "Refinement Processes" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("soc_proto.process.refine"), path_abbreviation: "comment_period", short_name: "Comment Period" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("soc_proto.process"), path_abbreviation: "align", short_name: "Unit Processes" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("soc_proto.process.align"), path_abbreviation: "vote", short_name: "Voting" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("soc_proto.process.align"), path_abbreviation: "consensus", short_name: "Consensus" },
+        PresetUnit { state: ALIVE_STATE, parent: Some("soc_proto.process.align"), path_abbreviation: "sortition", short_name: "Sortition" },
+    ],
+    documents: &[
+        PresetDocument {
+            path: "",
+            document_type: DOC_TREE_META,
+            content: &[
+                PresetSection {
+                    name: "Alignments",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"processRoot\":\"soc_proto.process\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/plain",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"title of the standard being defined\"}",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/markdown",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"A multi-sentence (short paragraph) description of the purpose towards which alignment is being sought.\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "projects",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Projects",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "Projects the group has taken on",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "soc_proto.process.define",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Definition Process Type",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "This process type is used to define some standard in the tree.   All definitions must have a context, as specification and a rationale.",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/plain:long",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"description of threshold for state to move to refinement\"}",
+                },
+                PresetSection {
+                    name: "context",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "text/plain:long",
+                    source_path: "soc_proto.process.define",
+                    content: "{\"description\": \"Description of the context and use-case an why it requires a standard. What problem does it solve? Why should someone want to implement this standard? What benefit does it provide to the Holochain ecosystem? What use cases does this standard address?\"}",
+                },
+                PresetSection {
+                    name: "specification",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "text/markdown",
+                    source_path: "soc_proto.process.define",
+                    content: "{\"description\": \"Description of the syntax, semantics, state diagrams, and workflows of any new feature/protocol/process. The specification should be detailed enough to allow for multiple interoperable implementations.\"}",
+                },
+                PresetSection {
+                    name: "rationale",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "text/plain:long",
+                    source_path: "soc_proto.process.define",
+                    content: "{\"description\": \"Description of what motivated the design and why particular design decisions were made. It should describe alternate designs that were considered and related work, e.g. how the feature is supported in other languages. The rationale may also provide evidence of consensus within the community, and should discuss important objections or concerns raised during discussion.\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "soc_proto.process.refine",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Refine Process Type",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "This process type is used to refine a standard in the tree.",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/plain:long",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"description of threshold for state to move to alignment\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "soc_proto.process.align",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Align Process Type",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "This process type is used to align on a standard after it has been defined and refined.",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_REQUIREMENT,
+                    content_type: "text/plain:long",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"description of threshold for state to move to alive\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "soc_proto.process.define.petition",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Definition by petition",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The petition definition variant allways includes a list of the petitioners",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "soc_proto.process.define",
+                    content: "A petition can move to refine when the threshold number of people have signed on to the petition.",
+                },
+                PresetSection {
+                    name: "petitioners",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "json/agents",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"who is making the petition\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "soc_proto.process.define.declaration",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Definition by declaration",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The declaration definition variant allways includes the zome signatures and an optional reference implementation",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "soc_proto.process.define",
+                    content: "A declaration can move to refinement whenever the stewards decide to.",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "soc_proto.process.refine.comment_period",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Comment Period",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The Comment Period refinement variant specifies a timeframe for commenting",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "soc_proto.process.refine",
+                    content: "Refinement can complete after the specified number of weeks in the period.",
+                },
+                PresetSection {
+                    name: "commenting",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "control/comments",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"commenting and making change suggestions\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "soc_proto.process.align.vote",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Votes",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The Votes alignment variant specifies a voting group, and a count of those votes",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "soc_proto.process.align",
+                    content: "Voting can move to Alive when the voting criteria have been fulfilled.",
+                },
+                PresetSection {
+                    name: "vote",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "control/voting",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"voting afforadance\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "soc_proto.process.align.consensus",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Consensus",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The Consensus alignment variant specifies an outcome of consensus decision that a group has taken",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "soc_proto.process.align",
+                    content: "Consensus can move to Alive when the consensus outcome has been recorded in the outcome section.",
+                },
+                PresetSection {
+                    name: "outcome",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "text/markdown",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"results of the consensus decision\"}",
+                },
+            ],
+        },
+        PresetDocument {
+            path: "soc_proto.process.align.sortition",
+            document_type: DOC_DOCUMENT,
+            content: &[
+                PresetSection {
+                    name: "title",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/plain",
+                    source_path: "",
+                    content: "Sortition",
+                },
+                PresetSection {
+                    name: "purpose",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "",
+                    content: "The Sortition alignment variant specifies a group of people who will decide on the unit and it's outcome of consensus decision that a group has taken",
+                },
+                PresetSection {
+                    name: "threshold",
+                    section_type: SECTION_TYPE_CONTENT,
+                    content_type: "text/markdown",
+                    source_path: "soc_proto.process.align",
+                    content: "Sortition can move to Alive when the sortition decision has been recorded in the outcome section by the stewards and the agents in the people section have all marked it as approved.",
+                },
+                PresetSection {
+                    name: "people",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "json/agents",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"list of people who will decide\"}",
+                },
+                PresetSection {
+                    name: "outcome",
+                    section_type: SECTION_TYPE_PROCESS,
+                    content_type: "text/markdown",
+                    source_path: SOURCE_MANUAL,
+                    content: "{\"description\": \"results of the sorition decision\"}",
+                },
+            ],
+        },
+    ],
+};
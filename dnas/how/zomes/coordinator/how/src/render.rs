@@ -0,0 +1,492 @@
+pub use hdk::prelude::*;
+use hdk::prelude::holo_hash::AgentPubKeyB64;
+use how_integrity::{ContentType, Document, Section};
+
+use crate::comment::{_get_comments, splice_utf16, steward_status, CommentOutput, CommentStatus};
+use crate::document::*;
+use crate::merge::document_path;
+use crate::unit::get_unit_entry;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum RenderFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderDocumentInput {
+    pub hash: EntryHash,
+    pub format: RenderFormat,
+    #[serde(default)]
+    pub include_comments: bool, // steward approved comments as footnotes
+}
+
+struct TitleBlock {
+    short_name: String,
+    path: String,
+    version: String,
+    state: String,
+    stewards: Vec<AgentPubKeyB64>,
+}
+
+struct Footnote {
+    number: usize,
+    section: String,
+    text: String,
+    after_section: bool, // its marker follows the section instead of the commented text
+}
+
+// markers spliced into structured content would break it, e.g. JSON or agent lists
+fn holds_markers(section: &Section) -> bool {
+    matches!(ContentType::parse(&section.content_type), ContentType::Markdown | ContentType::Other(_))
+}
+
+// comments become numbered footnotes in document order, with a marker at the end of the
+// commented text, or after the section if its content can't hold one.  Comments whose
+// text is gone go without a marker.
+fn footnote_sections(document: &Document, comments: Vec<CommentOutput>) -> (Vec<Section>, Vec<Footnote>) {
+    let mut sections = document.content.clone();
+    let mut footnotes = vec![];
+    for section in sections.iter_mut() {
+        let mut on_section: Vec<&CommentOutput> = comments.iter().filter(|c| c.comment.section == section.name).collect();
+        on_section.sort_by_key(|c| c.comment.end_offset);
+        let inline = holds_markers(section);
+        let mut markers = vec![];
+        for comment in on_section {
+            let number = footnotes.len() + 1;
+            if !comment.comment.orphaned && inline {
+                markers.push((comment.comment.end_offset, number));
+            }
+            footnotes.push(Footnote {
+                number,
+                section: section.name.clone(),
+                text: comment.comment.text.clone(),
+                after_section: !comment.comment.orphaned && !inline,
+            });
+        }
+        // from the back so that earlier offsets stay valid
+        for (offset, number) in markers.into_iter().rev() {
+            if let Some(content) = splice_utf16(&section.content, offset, offset, &format!("[^{}]", number)) {
+                section.content = content;
+            }
+        }
+    }
+    (sections, footnotes)
+}
+
+fn render_markdown_section(section: &Section) -> String {
+    match ContentType::parse(&section.content_type) {
+        ContentType::Markdown | ContentType::Other(_) => section.content.clone(),
+        ContentType::Json { .. } => format!("```json\n{}\n```", section.content),
+        ContentType::AgentList => serde_json::from_str::<Vec<String>>(&section.content)
+            .map(|agents| agents.iter().map(|a| format!("- `{}`", a)).collect::<Vec<String>>().join("\n"))
+            .unwrap_or_else(|_| section.content.clone()),
+        _ => section.content.clone(),
+    }
+}
+
+// the markers of the footnotes that follow a section rather than being in its text
+fn trailing_markers(section: &Section, footnotes: &[Footnote]) -> String {
+    footnotes.iter()
+        .filter(|f| f.after_section && f.section == section.name)
+        .map(|f| format!("[^{}]", f.number))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn render_markdown(title: &TitleBlock, sections: &[Section], footnotes: &[Footnote]) -> String {
+    let mut out = format!("# {}\n\n", title.short_name);
+    out.push_str(&format!("- **Path:** `{}`\n", title.path));
+    out.push_str(&format!("- **Version:** {}\n", title.version));
+    out.push_str(&format!("- **State:** {}\n", title.state));
+    let stewards: Vec<String> = title.stewards.iter().map(|s| format!("`{}`", s)).collect();
+    out.push_str(&format!("- **Stewards:** {}\n", stewards.join(", ")));
+    for section in sections {
+        out.push_str(&format!("\n## {}\n\n", section.name));
+        let mut content = String::from(render_markdown_section(section).trim_end());
+        let markers = trailing_markers(section, footnotes);
+        if !markers.is_empty() {
+            if !content.trim().is_empty() {
+                content.push_str("\n\n");
+            }
+            content.push_str(&markers);
+        }
+        if content.trim().is_empty() {
+            continue;
+        }
+        out.push_str(&content);
+        out.push('\n');
+    }
+    if !footnotes.is_empty() {
+        out.push('\n');
+        for footnote in footnotes {
+            out.push_str(&format!("[^{}]: ({}) {}\n", footnote.number, footnote.section, footnote.text.replace("\n", " ")));
+        }
+    }
+    out
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// only links that can't run script make it into the html
+fn safe_href(href: &str) -> bool {
+    let lower = href.trim().to_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://") || lower.starts_with("mailto:") || lower.starts_with('#')
+}
+
+// for every position, where pattern next starts at or after it, the length of chars once
+// it doesn't occur any more
+fn next_occurrences(chars: &[char], pattern: &str) -> Vec<usize> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let mut next = vec![chars.len(); chars.len() + 1];
+    for i in (0..chars.len()).rev() {
+        next[i] = if chars[i..].starts_with(&pattern) { i } else { next[i + 1] };
+    }
+    next
+}
+
+// Text with where each delimiter occurs next, so that looking for the end of a span is a
+// lookup rather than a scan of the rest of the text for every opener without one.
+struct InlineText {
+    chars: Vec<char>,
+    ticks: Vec<usize>,
+    stars: Vec<usize>,
+    double_stars: Vec<usize>,
+    brackets: Vec<usize>,
+    link_middles: Vec<usize>,
+    parens: Vec<usize>,
+}
+
+impl InlineText {
+    fn new(text: &str) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        InlineText {
+            ticks: next_occurrences(&chars, "`"),
+            stars: next_occurrences(&chars, "*"),
+            double_stars: next_occurrences(&chars, "**"),
+            brackets: next_occurrences(&chars, "]"),
+            link_middles: next_occurrences(&chars, "]("),
+            parens: next_occurrences(&chars, ")"),
+            chars,
+        }
+    }
+
+    // the next delimiter at or after from that ends before to
+    fn find(next: &[usize], from: usize, len: usize, to: usize) -> Option<usize> {
+        let at = *next.get(from)?;
+        if at + len <= to { Some(at) } else { None }
+    }
+
+    fn slice(&self, from: usize, to: usize) -> String {
+        self.chars[from..to].iter().collect()
+    }
+
+    fn render(&self, from: usize, to: usize) -> String {
+        let chars = &self.chars;
+        let mut out = String::new();
+        let mut i = from;
+        while i < to {
+            let c = chars[i];
+            if c == '`' {
+                if let Some(end) = Self::find(&self.ticks, i + 1, 1, to) {
+                    out.push_str(&format!("<code>{}</code>", html_escape(&self.slice(i + 1, end))));
+                    i = end + 1;
+                    continue;
+                }
+            } else if c == '*' && i + 1 < to && chars[i + 1] == '*' {
+                if let Some(end) = Self::find(&self.double_stars, i + 2, 2, to) {
+                    out.push_str(&format!("<strong>{}</strong>", self.render(i + 2, end)));
+                    i = end + 2;
+                    continue;
+                }
+            } else if c == '*' {
+                if let Some(end) = Self::find(&self.stars, i + 1, 1, to) {
+                    if end > i + 1 {
+                        out.push_str(&format!("<em>{}</em>", self.render(i + 1, end)));
+                        i = end + 1;
+                        continue;
+                    }
+                }
+            } else if c == '[' && i + 1 < to && chars[i + 1] == '^' {
+                if let Some(end) = Self::find(&self.brackets, i + 2, 1, to) {
+                    let number = self.slice(i + 2, end);
+                    if !number.is_empty() && number.chars().all(|d| d.is_ascii_digit()) {
+                        out.push_str(&format!("<sup id=\"fnref-{0}\"><a href=\"#fn-{0}\">{0}</a></sup>", number));
+                        i = end + 1;
+                        continue;
+                    }
+                }
+            } else if c == '[' {
+                if let Some(close) = Self::find(&self.link_middles, i + 1, 2, to) {
+                    if let Some(end) = Self::find(&self.parens, close + 2, 1, to) {
+                        let label = self.render(i + 1, close);
+                        let href = self.slice(close + 2, end);
+                        if safe_href(&href) {
+                            out.push_str(&format!("<a href=\"{}\">{}</a>", html_escape(href.trim()), label));
+                        } else {
+                            out.push_str(&label);
+                        }
+                        i = end + 1;
+                        continue;
+                    }
+                }
+            }
+            out.push_str(&html_escape(&c.to_string()));
+            i += 1;
+        }
+        out
+    }
+}
+
+// the inline markdown we support: `code`, **strong**, *emphasis*, [links](url) and [^n]
+// footnote markers.  Everything else is escaped text, so no html from the content survives.
+fn render_inline(text: &str) -> String {
+    let text = InlineText::new(text);
+    text.render(0, text.chars.len())
+}
+
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = trimmed.strip_prefix(bullet) {
+            return Some((false, item));
+        }
+    }
+    let digits = trimmed.len() - trimmed.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 {
+        if let Some(item) = trimmed[digits..].strip_prefix(". ") {
+            return Some((true, item));
+        }
+    }
+    None
+}
+
+/// renders markdown block by block: headings, fenced code, lists and paragraphs
+fn markdown_to_html(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = vec![];
+    let mut list: Option<bool> = None; // whether the open list is ordered
+    let mut code: Option<Vec<&str>> = None;
+    let flush_paragraph = |out: &mut String, paragraph: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            out.push_str(&format!("<p>{}</p>\n", render_inline(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+    };
+    let close_list = |out: &mut String, list: &mut Option<bool>| {
+        if let Some(ordered) = list.take() {
+            out.push_str(if ordered { "</ol>\n" } else { "</ul>\n" });
+        }
+    };
+    for line in markdown.lines() {
+        if let Some(lines) = code.as_mut() {
+            if line.trim_start().starts_with("```") {
+                out.push_str(&format!("<pre><code>{}</code></pre>\n", html_escape(&lines.join("\n"))));
+                code = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+        if line.trim_start().starts_with("```") {
+            flush_paragraph(&mut out, &mut paragraph);
+            close_list(&mut out, &mut list);
+            code = Some(vec![]);
+            continue;
+        }
+        if line.trim().is_empty() {
+            flush_paragraph(&mut out, &mut paragraph);
+            close_list(&mut out, &mut list);
+            continue;
+        }
+        let hashes = line.len() - line.trim_start_matches('#').len();
+        if hashes > 0 && hashes <= 6 && line[hashes..].starts_with(' ') {
+            flush_paragraph(&mut out, &mut paragraph);
+            close_list(&mut out, &mut list);
+            // section headings are h2, so headings inside a section start at h3
+            let level = (hashes + 2).min(6);
+            out.push_str(&format!("<h{0}>{1}</h{0}>\n", level, render_inline(line[hashes..].trim())));
+            continue;
+        }
+        if let Some((ordered, item)) = list_item(line) {
+            flush_paragraph(&mut out, &mut paragraph);
+            if list != Some(ordered) {
+                close_list(&mut out, &mut list);
+                out.push_str(if ordered { "<ol>\n" } else { "<ul>\n" });
+                list = Some(ordered);
+            }
+            out.push_str(&format!("<li>{}</li>\n", render_inline(item)));
+            continue;
+        }
+        close_list(&mut out, &mut list);
+        paragraph.push(line.trim());
+    }
+    if let Some(lines) = code {
+        out.push_str(&format!("<pre><code>{}</code></pre>\n", html_escape(&lines.join("\n"))));
+    }
+    flush_paragraph(&mut out, &mut paragraph);
+    close_list(&mut out, &mut list);
+    out
+}
+
+fn render_html_section(section: &Section) -> String {
+    match ContentType::parse(&section.content_type) {
+        ContentType::Markdown => markdown_to_html(&section.content),
+        ContentType::Json { .. } => format!("<pre><code>{}</code></pre>\n", html_escape(&section.content)),
+        ContentType::AgentList => match serde_json::from_str::<Vec<String>>(&section.content) {
+            Ok(agents) => format!("<ul>\n{}</ul>\n", agents.iter().map(|a| format!("<li><code>{}</code></li>\n", html_escape(a))).collect::<String>()),
+            Err(_) => format!("<p>{}</p>\n", html_escape(&section.content)),
+        },
+        _ => section.content.split("\n\n")
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| format!("<p>{}</p>\n", render_inline(p)))
+            .collect(),
+    }
+}
+
+fn render_html(title: &TitleBlock, sections: &[Section], footnotes: &[Footnote]) -> String {
+    let mut out = String::from("<article>\n<header>\n");
+    out.push_str(&format!("<h1>{}</h1>\n<dl>\n", html_escape(&title.short_name)));
+    out.push_str(&format!("<dt>Path</dt><dd><code>{}</code></dd>\n", html_escape(&title.path)));
+    out.push_str(&format!("<dt>Version</dt><dd>{}</dd>\n", html_escape(&title.version)));
+    out.push_str(&format!("<dt>State</dt><dd>{}</dd>\n", html_escape(&title.state)));
+    let stewards: Vec<String> = title.stewards.iter().map(|s| format!("<code>{}</code>", html_escape(&s.to_string()))).collect();
+    out.push_str(&format!("<dt>Stewards</dt><dd>{}</dd>\n</dl>\n</header>\n", stewards.join(", ")));
+    for section in sections {
+        out.push_str(&format!("<section>\n<h2>{}</h2>\n", html_escape(&section.name)));
+        out.push_str(&render_html_section(section));
+        let markers = trailing_markers(section, footnotes);
+        if !markers.is_empty() {
+            out.push_str(&format!("<p>{}</p>\n", render_inline(&markers)));
+        }
+        out.push_str("</section>\n");
+    }
+    if !footnotes.is_empty() {
+        out.push_str("<footer>\n<ol class=\"footnotes\">\n");
+        for footnote in footnotes {
+            out.push_str(&format!("<li id=\"fn-{}\">({}) {}</li>\n", footnote.number, html_escape(&footnote.section), render_inline(&footnote.text)));
+        }
+        out.push_str("</ol>\n</footer>\n");
+    }
+    out.push_str("</article>\n");
+    out
+}
+
+/// renders a document as Markdown or sanitized HTML for publishing outside of the hApp
+#[hdk_extern]
+pub fn render_document(input: RenderDocumentInput) -> ExternResult<String> {
+    let document = get_document_entry(input.hash.clone())?;
+    let unit = get_unit_entry(document.unit_hash.clone())?;
+    let title = TitleBlock {
+        short_name: unit.short_name.clone(),
+        path: document_path(&document)?,
+        version: unit.version.clone(),
+        state: document.state.clone(),
+        stewards: unit.stewards.clone(),
+    };
+    let mut comments = vec![];
+    if input.include_comments {
+        for comment in _get_comments(input.hash)? {
+            if steward_status(&comment, &unit.stewards)? == Some(CommentStatus::Approved) {
+                comments.push(comment);
+            }
+        }
+    }
+    let (sections, footnotes) = footnote_sections(&document, comments);
+    let sections: Vec<Section> = sections.into_iter().filter(|s| s.section_type == SECTION_TYPE_CONTENT).collect();
+    Ok(match input.format {
+        RenderFormat::Markdown => render_markdown(&title, &sections, &footnotes),
+        RenderFormat::Html => render_html(&title, &sections, &footnotes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(html_escape("<a href=\"x\">'&'</a>"), "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;");
+        assert_eq!(render_inline("<script>alert(1)</script>"), "&lt;script&gt;alert(1)&lt;/script&gt;");
+    }
+
+    #[test]
+    fn only_links_that_cant_run_script_are_kept() {
+        assert!(safe_href("https://example.org"));
+        assert!(safe_href(" HTTP://example.org"));
+        assert!(safe_href("mailto:someone@example.org"));
+        assert!(safe_href("#fn-1"));
+        assert!(!safe_href("javascript:alert(1)"));
+        assert!(!safe_href(" JavaScript:alert(1)"));
+        assert!(!safe_href("data:text/html,<script>"));
+        assert!(!safe_href("/relative"));
+        assert_eq!(render_inline("[click](javascript:steal)"), "click");
+        assert_eq!(
+            render_inline("[a \"b\"](https://example.org/?q=\"><script>)"),
+            "<a href=\"https://example.org/?q=&quot;&gt;&lt;script&gt;\">a &quot;b&quot;</a>",
+        );
+    }
+
+    #[test]
+    fn renders_inline_markdown() {
+        assert_eq!(
+            render_inline("**bold *and* em** `<b>` [^2]"),
+            "<strong>bold <em>and</em> em</strong> <code>&lt;b&gt;</code> <sup id=\"fnref-2\"><a href=\"#fn-2\">2</a></sup>",
+        );
+        // markers without their closing half are text
+        assert_eq!(render_inline("2 * 3 [^x] `a"), "2 * 3 [^x] `a");
+    }
+
+    #[test]
+    fn unmatched_openers_dont_rescan_the_text() {
+        let text = format!("{}{}", "[".repeat(50_000), "`*");
+        assert_eq!(render_inline(&text), text);
+    }
+
+    fn title() -> TitleBlock {
+        TitleBlock {
+            short_name: String::from("A"),
+            path: String::from("a"),
+            version: String::from("vidx:1"),
+            state: String::from("define"),
+            stewards: vec![],
+        }
+    }
+
+    #[test]
+    fn footnote_markers_follow_structured_sections() {
+        let sections = vec![
+            Section::new("data", "c", "application/json", "", None, "{\"a\":1}"),
+            Section::new("notes", "c", "text/markdown", "", None, "fine[^2]"),
+        ];
+        let footnotes = vec![
+            Footnote { number: 1, section: String::from("data"), text: String::from("why a?"), after_section: true },
+            Footnote { number: 2, section: String::from("notes"), text: String::from("ok"), after_section: false },
+        ];
+        let markdown = render_markdown(&title(), &sections, &footnotes);
+        assert!(markdown.contains("## data\n\n```json\n{\"a\":1}\n```\n\n[^1]\n\n## notes\n\nfine[^2]\n"));
+        assert!(markdown.ends_with("\n[^1]: (data) why a?\n[^2]: (notes) ok\n"));
+        let html = render_html(&title(), &sections, &footnotes);
+        assert!(html.contains("<pre><code>{&quot;a&quot;:1}</code></pre>\n<p><sup id=\"fnref-1\"><a href=\"#fn-1\">1</a></sup></p>\n</section>"));
+    }
+
+    #[test]
+    fn renders_markdown_blocks() {
+        let markdown = "# Head\npara one\nline two\n\n- a\n- <b>\n1. x\n\n```\n<script>\n```\n<div onclick=x>hi</div>";
+        assert_eq!(markdown_to_html(markdown), concat!(
+            "<h3>Head</h3>\n",
+            "<p>para one line two</p>\n",
+            "<ul>\n<li>a</li>\n<li>&lt;b&gt;</li>\n</ul>\n",
+            "<ol>\n<li>x</li>\n</ol>\n",
+            "<pre><code>&lt;script&gt;</code></pre>\n",
+            "<p>&lt;div onclick=x&gt;hi&lt;/div&gt;</p>\n",
+        ));
+    }
+}
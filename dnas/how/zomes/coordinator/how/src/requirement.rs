@@ -0,0 +1,105 @@
+pub use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use how_integrity::{Document, RequirementDef};
+
+use crate::document::*;
+use crate::template::definitions_from_hierarchy;
+use crate::unit::get_unit_entry;
+
+/// why a document doesn't meet a requirement
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", content = "content")]
+pub enum RequirementFailure {
+    Missing,
+    ContentType { expected: String, actual: String },
+    TooShort { min: usize, actual: usize },
+    TooLong { max: usize, actual: usize },
+    NotFilledIn { state: String },
+    NoEligibleEditor,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequirementResult {
+    pub section: String, // the required section, the definition doesn't carry its name when serialized
+    pub requirement: RequirementDef,
+    pub source_path: String, // where in the tree the requirement is defined
+    pub passed: bool,
+    pub failures: Vec<RequirementFailure>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequirementsReport {
+    pub document_hash: EntryHashB64,
+    pub state: String,
+    pub passed: bool,
+    pub results: Vec<RequirementResult>,
+}
+
+fn check_requirement(def: &RequirementDef, document: &Document) -> Vec<RequirementFailure> {
+    let mut failures = vec![];
+    let section = match document.content.iter().find(|s| s.name == def.section) {
+        Some(section) => section,
+        None => return vec![RequirementFailure::Missing],
+    };
+    if let Some(expected) = &def.content_type {
+        if *expected != section.content_type {
+            failures.push(RequirementFailure::ContentType { expected: expected.clone(), actual: section.content_type.clone() });
+        }
+    }
+    let length = section.content.chars().count();
+    if length == 0 {
+        if def.must_be_filled(&document.state) {
+            failures.push(RequirementFailure::NotFilledIn { state: def.filled_in_state.clone().unwrap_or_default() });
+        }
+    } else {
+        if let Some(min) = def.min_length {
+            if length < min {
+                failures.push(RequirementFailure::TooShort { min, actual: length });
+            }
+        }
+        if let Some(max) = def.max_length {
+            if length > max {
+                failures.push(RequirementFailure::TooLong { max, actual: length });
+            }
+        }
+    }
+    // someone who may edit the document has to be eligible to fill in the section
+    if let Some(eligible) = &def.editors {
+        if !document.editors.iter().any(|e| eligible.contains(e)) {
+            failures.push(RequirementFailure::NoEligibleEditor);
+        }
+    }
+    failures
+}
+
+/// checks a document against the requirement sections defined above its unit in the tree
+#[hdk_extern]
+pub fn check_requirements(document_hash: EntryHash) -> ExternResult<RequirementsReport> {
+    let document = get_document_entry(document_hash.clone())?;
+    let unit = get_unit_entry(document.unit_hash.clone())?;
+    let parent = unit.parents.first().cloned().unwrap_or_default();
+
+    let mut results: Vec<RequirementResult> = vec![];
+    for inherited in definitions_from_hierarchy(&parent, 0, SECTION_TYPE_REQUIREMENT)? {
+        if results.iter().any(|r| r.section == inherited.section.name) {
+            continue;
+        }
+        let requirement = RequirementDef::from_section(&inherited.section);
+        let failures = check_requirement(&requirement, &document);
+        results.push(RequirementResult {
+            section: requirement.section.clone(),
+            requirement,
+            source_path: inherited.path,
+            passed: failures.is_empty(),
+            failures,
+        });
+    }
+    Ok(RequirementsReport {
+        document_hash: document_hash.into(),
+        state: document.state,
+        passed: results.iter().all(|r| r.passed),
+        results,
+    })
+}
@@ -0,0 +1,347 @@
+pub use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use how_integrity::{Document, DocumentMeta, Section, Unit, LinkTypes};
+
+use crate::diff::{diff_text, DiffGranularity, DiffHunk};
+use crate::document::*;
+use crate::error::*;
+use crate::merge::{document_path, merge_section};
+use crate::tree::tree_path;
+use crate::unit::{convert_tag, get_unit_entry, get_units_path};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionsFromHierarchyInput {
+    pub path: String,
+    pub section_type: String,
+    #[serde(default)]
+    pub start: usize, // number of leading path segments to skip, process paths skip their type and name
+}
+
+/// a section inherited from the tree along with where it was found
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InheritedSection {
+    pub path: String,
+    pub unit_hash: EntryHash,
+    pub document_hash: EntryHashB64,
+    pub section: Section,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateTemplateInput {
+    pub unit_hash: EntryHash,
+    pub template_path: String,
+}
+
+// sections copied out of the tree become content of the new document, remembering where
+// they came from so that the source can be found again
+fn sourced_section(section: &Section, source_path: &str, source_unit: &EntryHash, keep_content: bool) -> Section {
+    let mut section = section.clone();
+    if !keep_content {
+        section.content = String::from("");
+    }
+    section.section_type = String::from(SECTION_TYPE_CONTENT);
+    section.source_path = String::from(source_path);
+    section.source_unit = Some(source_unit.clone());
+    section
+}
+
+/// walks down the tree along path, starting at segment start, collecting the sections of
+/// section_type from the current documents at each level.  `_template` documents along the
+/// way contribute all their sections, boilerplate content included.
+pub fn sections_from_hierarchy(path: &str, start: usize, section_type: &str) -> ExternResult<Vec<InheritedSection>> {
+    walk_hierarchy(path, start, section_type, true)
+}
+
+/// the sections of section_type along path as they are in their documents, i.e. the
+/// definitions rather than what a document inheriting them starts out with.  For
+/// requirements these include the sections of `_template` documents.
+pub fn definitions_from_hierarchy(path: &str, start: usize, section_type: &str) -> ExternResult<Vec<InheritedSection>> {
+    walk_hierarchy(path, start, section_type, false)
+}
+
+fn walk_hierarchy(path: &str, start: usize, section_type: &str, inherit: bool) -> ExternResult<Vec<InheritedSection>> {
+    let segments: Vec<&str> = path.split(".").filter(|s| !s.is_empty()).collect();
+    let mut sections: Vec<InheritedSection> = vec![];
+    for i in start..=segments.len() {
+        let walk = segments[0..i].join(".");
+        let docs = get_documents_inner(tree_path(walk.clone()).path_entry_hash()?)?;
+        for doc in docs.into_iter().filter(|d| d.updated_by.is_empty() && d.deleted_by.is_empty()) {
+            let document = doc.content;
+            let inherited: Vec<Section> = if document.document_type == DOC_DOCUMENT {
+                document.content.iter()
+                    .filter(|s| s.section_type == section_type)
+                    .map(|s| if inherit { sourced_section(s, &walk, &document.unit_hash, false) } else { s.clone() })
+                    .collect()
+            } else if document.document_type == DOC_TEMPLATE && section_type == SECTION_TYPE_REQUIREMENT {
+                // every section of a template is required of the documents below it
+                document.content.iter()
+                    .map(|s| if inherit { sourced_section(s, &walk, &document.unit_hash, true) } else { s.clone() })
+                    .collect()
+            } else {
+                vec![]
+            };
+            for section in inherited {
+                sections.push(InheritedSection {
+                    path: walk.clone(),
+                    unit_hash: document.unit_hash.clone(),
+                    document_hash: doc.hash.clone(),
+                    section,
+                });
+            }
+        }
+    }
+    Ok(sections)
+}
+
+#[hdk_extern]
+pub fn get_sections_from_hierarchy(input: SectionsFromHierarchyInput) -> ExternResult<Vec<InheritedSection>> {
+    sections_from_hierarchy(&input.path, input.start, &input.section_type)
+}
+
+// the process whose template sections a document needs in the given state
+pub fn process_path_for_state(unit: &Unit, state: &str) -> Option<String> {
+    let idx = match state {
+        "define" => 0,
+        "refine" => 1,
+        "align" => 2,
+        _ => return None,
+    };
+    unit.processes.get(idx).map(|(process_type, process_name)| format!("{}.{}", process_type, process_name))
+}
+
+/// adds the inherited sections the document doesn't have yet, remembering which template
+/// revision each was taken from
+pub fn append_sections(document: &mut Document, sections: Vec<InheritedSection>) {
+    for inherited in sections {
+        if !document.content.iter().any(|s| s.name == inherited.section.name) {
+            document.meta.templates.insert(inherited.section.name.clone(), inherited.document_hash.to_string());
+            document.content.push(inherited.section);
+        }
+    }
+}
+
+fn get_unit_state(unit_hash: &EntryHash) -> ExternResult<String> {
+    let target: AnyLinkableHash = unit_hash.clone().into();
+    let link = get_links(get_units_path().path_entry_hash()?, LinkTypes::Unit, None)?
+        .into_iter()
+        .find(|l| l.target == target)
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Unit not found"))))?;
+    let (state, _, _) = convert_tag(link.tag)?;
+    Ok(state)
+}
+
+/// builds the initial document of a unit from the required sections along template_path
+/// and the process sections of the unit's first process
+pub fn build_initial_document(unit_hash: EntryHash, unit: &Unit, template_path: &str) -> ExternResult<Document> {
+    let mut document = Document {
+        unit_hash: unit_hash.clone(),
+        document_type: String::from(DOC_DOCUMENT),
+        state: get_unit_state(&unit_hash)?,
+        editors: unit.stewards.clone(),
+        content: vec![],
+        meta: DocumentMeta::new(),
+    };
+    append_sections(&mut document, sections_from_hierarchy(template_path, 0, SECTION_TYPE_REQUIREMENT)?);
+    if let Some((process_type, process_name)) = unit.processes.first() {
+        let process_path = format!("{}.{}", process_type, process_name);
+        append_sections(&mut document, sections_from_hierarchy(&process_path, 2, SECTION_TYPE_PROCESS)?);
+    }
+    if let Some(title) = document.content.iter_mut().find(|s| s.name == "title") {
+        title.content = unit.short_name.clone();
+    }
+    Ok(document)
+}
+
+/// creates the initial document of a unit and links it at the unit's path
+#[hdk_extern]
+pub fn instantiate_template(input: InstantiateTemplateInput) -> ExternResult<EntryHashB64> {
+    let unit = get_unit_entry(input.unit_hash.clone())?;
+    let document = build_initial_document(input.unit_hash, &unit, &input.template_path)?;
+    let path = unit.path_str()?;
+    let hash = create_document(DocumentInput { path, document })?;
+    Ok(hash)
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum DriftStatus {
+    Current,
+    Drifted,
+    Unknown, // the document is from before template revisions were recorded
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateDrift {
+    pub section: String,
+    pub source_path: String,
+    pub source_unit: EntryHash,
+    pub recorded_revision: Option<EntryHashB64>, // none for documents from before revisions were recorded
+    pub current_revision: Option<EntryHashB64>, // none if the template no longer has the section
+    pub status: DriftStatus,
+    pub changes: Vec<DiffHunk>, // from the recorded template text to the current one, none if unknown
+}
+
+// the section a document section was taken from, in the template's current revision
+fn current_template_section(source_unit: &EntryHash, name: &str) -> HowResult<Option<(EntryHashB64, Section)>> {
+    let head = match _get_current_document(source_unit.clone()) {
+        Ok(CurrentDocument::Head(head)) => head,
+        Ok(CurrentDocument::Conflict(_)) => return Err(HowError::DocumentConflict),
+        Err(HowError::DocumentNotFound) => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let section = head.content.content.into_iter().find(|s| s.name == name);
+    Ok(section.map(|s| (head.hash, s)))
+}
+
+fn template_section(revision: &EntryHashB64, name: &str) -> HowResult<Option<Section>> {
+    let document = get_document_entry(EntryHash::from(revision.clone()))?;
+    Ok(document.content.into_iter().find(|s| s.name == name))
+}
+
+fn _get_template_drift(document: &Document) -> HowResult<Vec<TemplateDrift>> {
+    let mut drift = vec![];
+    for section in document.content.iter().filter(|s| s.section_type == SECTION_TYPE_CONTENT) {
+        let source_unit = match &section.source_unit {
+            Some(source_unit) if section.source_path != SOURCE_MANUAL => source_unit,
+            _ => continue,
+        };
+        let recorded_revision = match document.meta.templates.get(&section.name) {
+            Some(revision) => Some(EntryHashB64::from_b64_str(revision).map_err(|_| HowError::HashConversionError)?),
+            None => None,
+        };
+        let recorded = match &recorded_revision {
+            Some(revision) => template_section(revision, &section.name)?,
+            None => None,
+        };
+        let current = current_template_section(source_unit, &section.name)?;
+        // only sections that came from a process template are of interest here
+        let template = current.as_ref().map(|(_, s)| s).or(recorded.as_ref());
+        if template.map(|s| s.section_type != SECTION_TYPE_PROCESS).unwrap_or(true) {
+            continue;
+        }
+        let (current_revision, current_text) = match current {
+            Some((revision, template)) => (Some(revision), template.content),
+            None => (None, String::from("")),
+        };
+        // without a recorded revision there is nothing to compare the template with
+        let (status, changes) = match &recorded_revision {
+            None => (DriftStatus::Unknown, vec![]),
+            Some(_) => {
+                let recorded_text = recorded.map(|s| s.content).unwrap_or_default();
+                let status = if recorded_revision == current_revision { DriftStatus::Current } else { DriftStatus::Drifted };
+                (status, diff_text(&recorded_text, &current_text, &DiffGranularity::Line))
+            }
+        };
+        drift.push(TemplateDrift {
+            section: section.name.clone(),
+            source_path: section.source_path.clone(),
+            source_unit: source_unit.clone(),
+            status,
+            changes,
+            recorded_revision,
+            current_revision,
+        });
+    }
+    Ok(drift)
+}
+
+/// compares each process sourced section of a document with the current revision of the
+/// template it was taken from
+#[hdk_extern]
+pub fn get_template_drift(document_hash: EntryHash) -> ExternResult<Vec<TemplateDrift>> {
+    let document = get_document_entry(document_hash)?;
+    Ok(_get_template_drift(&document)?)
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Default)]
+pub enum UpgradeMode {
+    #[default]
+    Merge, // the template's changes are merged into what the authors wrote
+    Replace, // the sections start over from the current template text
+    RecordOnly, // only the revision is recorded, the changes are left to the authors
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeTemplateInput {
+    pub document_hash: EntryHash,
+    pub sections: Vec<String>,
+    #[serde(default)]
+    pub mode: UpgradeMode,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeTemplateOutput {
+    pub hash: EntryHashB64,
+    pub upgraded: Vec<TemplateDrift>, // how each template changed since the revision the section was taken from
+    pub conflicts: Vec<String>, // sections the authors and the template both changed, kept between markers
+}
+
+/// Moves the chosen sections to the current revision of their template and brings in
+/// its text.  By default the template's changes since the recorded revision are merged
+/// into what the authors wrote, like merge_documents merges sections, and sections both
+/// changed are left as conflicts.  Sections nobody has written in yet take the template
+/// text as it is.  Replace starts the sections over from the template text, RecordOnly
+/// just records the revision and hands the changes back for the authors to act on.
+#[hdk_extern]
+pub fn upgrade_document_template(input: UpgradeTemplateInput) -> ExternResult<UpgradeTemplateOutput> {
+    let mut document = get_document_entry(input.document_hash.clone())?;
+    let drift = _get_template_drift(&document)?;
+    let mut upgraded = vec![];
+    let mut conflicts = vec![];
+    for name in input.sections.iter() {
+        let section_drift = drift.iter()
+            .find(|d| d.section == *name)
+            .cloned()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(format!("Section {} was not taken from a process template", name))))?;
+        let revision = section_drift.current_revision.clone()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(format!("Template no longer has section {}", name))))?;
+        let template = template_section(&revision, name)?
+            .ok_or(wasm_error!(WasmErrorInner::Guest(format!("Template no longer has section {}", name))))?;
+        let template = sourced_section(&template, &section_drift.source_path, &section_drift.source_unit, true);
+        let recorded = match &section_drift.recorded_revision {
+            Some(recorded) => template_section(recorded, name)?
+                .map(|s| sourced_section(&s, &section_drift.source_path, &section_drift.source_unit, true)),
+            None => None,
+        };
+        if let Some(section) = document.content.iter_mut().find(|s| s.name == *name) {
+            match input.mode {
+                UpgradeMode::Merge => {
+                    let base = if section.content.is_empty() { Some(section.clone()) } else { recorded };
+                    let (merged, conflict) = merge_section(base.as_ref(), Some(&*section), Some(&template));
+                    if let Some(merged) = merged {
+                        *section = merged;
+                    }
+                    if conflict {
+                        conflicts.push(name.clone());
+                    }
+                }
+                UpgradeMode::Replace => *section = template,
+                UpgradeMode::RecordOnly => (),
+            }
+        }
+        document.meta.templates.insert(name.clone(), revision.to_string());
+        upgraded.push(section_drift);
+    }
+    if !conflicts.is_empty() {
+        document.meta.upgrade();
+        let recorded = document.meta.merge_conflicts.get_or_insert_with(Vec::new);
+        for name in conflicts.iter() {
+            if !recorded.contains(name) {
+                recorded.push(name.clone());
+            }
+        }
+    }
+    let path = document_path(&document)?;
+    let hash = update_document(UpdateDocumentInput {
+        hash: input.document_hash.clone().into(),
+        path,
+        document,
+        expected_head: Some(input.document_hash.into()),
+    })?;
+    Ok(UpgradeTemplateOutput { hash, upgraded, conflicts })
+}
@@ -1,8 +1,11 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
 pub use hdk::prelude::*;
 pub use hdk::hash_path::path::TypedPath;
-use how_integrity::{TREE_ROOT, LinkTypes};
+use how_integrity::{TREE_ROOT, LinkTypes, Document};
 
 use crate::{unit::convert_tag, HowError};
+use crate::document::{get_entry_details, DOC_COMMENT};
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct UnitInfo {
@@ -27,7 +30,7 @@ pub struct PathContent {
 }
 
 
-fn get_entry_hashes(path: &Path) -> ExternResult<(Vec<UnitInfo>,Vec<EntryHash>)> {
+pub fn get_entry_hashes(path: &Path) -> ExternResult<(Vec<UnitInfo>,Vec<EntryHash>)> {
     let mut units = vec![];
     let mut documents = vec![];
     let links = get_links(path.path_entry_hash()?, vec![LinkTypes::Unit, LinkTypes::Document], None)?;
@@ -98,6 +101,48 @@ pub fn tree_path(path_str: String) -> Path {
     Path::from(path)
 }
 
+// how many redirect links we will follow before giving up, guards against redirect loops
+pub const MAX_REDIRECTS: usize = 16;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedPath {
+    pub path: String,
+    pub redirected_from: Option<String>,
+}
+
+// follows Redirect links left behind when a unit is moved or renamed until we reach a path
+// that actually has units on it
+pub fn resolve_path(path_str: String) -> ExternResult<ResolvedPath> {
+    let mut current = path_str.clone();
+    let mut hops = 0;
+    loop {
+        let path = tree_path(current.clone());
+        let (units, _) = get_entry_hashes(&path)?;
+        if units.len() > 0 {
+            break;
+        }
+        let links = get_links(path.path_entry_hash()?, LinkTypes::Redirect, None)?;
+        // if a path was redirected more than once the most recent redirect wins
+        match links.into_iter().max_by_key(|l| l.timestamp) {
+            Some(link) => {
+                current = String::from_utf8(link.tag.into_inner())
+                    .map_err(|_e| wasm_error!(WasmErrorInner::Guest(String::from("could not convert link tag to string"))))?;
+            }
+            None => break,
+        }
+        hops += 1;
+        if hops > MAX_REDIRECTS {
+            return Err(HowError::TooManyRedirects(path_str).into());
+        }
+    }
+    let redirected_from = if current != path_str { Some(path_str) } else { None };
+    Ok(ResolvedPath {
+        path: current,
+        redirected_from,
+    })
+}
+
 pub fn tree_path_to_str(path:Path) -> ExternResult<String>  {
     let mut v = path.as_ref().clone();
     v.remove(0);
@@ -138,6 +183,148 @@ pub fn _get_path_tree(root_path: Path) -> ExternResult<Tree<PathContent>> {
     Ok(tree)
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeStats {
+    pub path: String,
+    pub units_by_state: BTreeMap<String, u32>,
+    pub documents: u32,
+    pub comments: u32,
+    pub contributors: u32,
+    pub last_activity: Option<Timestamp>,
+}
+
+// everything seen in a subtree, kept as sets so that units and documents reachable
+// through more than one parent are only counted once
+#[derive(Default)]
+struct StatsAccumulator {
+    units: HashMap<EntryHash, String>,
+    documents: HashSet<EntryHash>,
+    comments: HashSet<EntryHash>,
+    contributors: HashSet<AgentPubKey>,
+    last_activity: Option<Timestamp>,
+}
+
+impl StatsAccumulator {
+    fn touch(&mut self, author: &AgentPubKey, timestamp: Timestamp) {
+        self.contributors.insert(author.clone());
+        if self.last_activity.map_or(true, |t| timestamp > t) {
+            self.last_activity = Some(timestamp);
+        }
+    }
+
+    fn merge(&mut self, other: &StatsAccumulator) {
+        self.units.extend(other.units.clone());
+        self.documents.extend(other.documents.clone());
+        self.comments.extend(other.comments.clone());
+        self.contributors.extend(other.contributors.clone());
+        if let Some(t) = other.last_activity {
+            if self.last_activity.map_or(true, |l| t > l) {
+                self.last_activity = Some(t);
+            }
+        }
+    }
+
+    fn stats(&self, path: String) -> TreeStats {
+        let mut units_by_state = BTreeMap::new();
+        for state in self.units.values() {
+            *units_by_state.entry(state.clone()).or_insert(0) += 1;
+        }
+        TreeStats {
+            path,
+            units_by_state,
+            documents: self.documents.len() as u32,
+            comments: self.comments.len() as u32,
+            contributors: self.contributors.len() as u32,
+            last_activity: self.last_activity,
+        }
+    }
+}
+
+// Reads the same unit and document links as get_entry_hashes.  Every revision of a
+// document is linked at its path, so only those nothing updated or deleted count as
+// documents.  Contributors are whoever wrote the units and revisions, not whoever
+// linked them, and so is the activity.
+fn get_path_stats(path: &Path) -> ExternResult<StatsAccumulator> {
+    let mut acc = StatsAccumulator::default();
+    let details = get_link_details(path.path_entry_hash()?, vec![LinkTypes::Unit, LinkTypes::Document], None)?;
+    for (create, deletes) in details.into_inner() {
+        if !deletes.is_empty() {
+            continue;
+        }
+        let link = match create.action() {
+            Action::CreateLink(link) => link.clone(),
+            _ => continue,
+        };
+        let link_type = LinkTypes::try_from(ScopedLinkType {
+            zome_index: link.zome_index,
+            zome_type: link.link_type,
+        })?;
+        let target: EntryHash = link.target_address.try_into().map_err(|_e| HowError::HashConversionError)?;
+        let entry_details = match get_entry_details(target.clone())? {
+            Some(entry_details) => entry_details,
+            None => continue,
+        };
+        for action in entry_details.actions.iter() {
+            acc.touch(action.action().author(), action.action().timestamp());
+        }
+        match link_type {
+            LinkTypes::Unit => {
+                let (state, _, _) = convert_tag(link.tag)?;
+                acc.units.insert(target, state);
+            }
+            LinkTypes::Document => {
+                if !entry_details.updates.is_empty() || !entry_details.deletes.is_empty() {
+                    continue;
+                }
+                let tag = String::from_utf8(link.tag.into_inner()).unwrap_or_default();
+                // documents linked before links were tagged have to be fetched to find out their type
+                let document_type = if !tag.is_empty() {
+                    tag
+                } else {
+                    match get(target.clone(), GetOptions::default())? {
+                        Some(record) => {
+                            let doc: Option<Document> = record.entry().to_app_option().map_err(|e| wasm_error!(e))?;
+                            doc.map(|d| d.document_type).unwrap_or_default()
+                        }
+                        None => String::from(""),
+                    }
+                };
+                if document_type == DOC_COMMENT {
+                    acc.comments.insert(target);
+                } else {
+                    acc.documents.insert(target);
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(acc)
+}
+
+fn build_stats_tree(tree: &mut Tree<TreeStats>, node: usize, path: Path) -> ExternResult<StatsAccumulator> {
+    let mut acc = get_path_stats(&path)?;
+    for child in path.clone().into_typed(ScopedLinkType::try_from(LinkTypes::Tree)?).children_paths()? {
+        let (units, _) = get_entry_hashes(&child)?;
+        // as in build_tree, ignore paths left empty by a move
+        if !units.is_empty() {
+            let idx = tree.insert(node, TreeStats::default());
+            let child_acc = build_stats_tree(tree, idx, child.path)?;
+            acc.merge(&child_acc);
+        }
+    }
+    tree.tree[node].val = acc.stats(tree_path_to_str(path)?);
+    Ok(acc)
+}
+
+/// returns stats for every node in the tree below path, each node covering its whole subtree
+#[hdk_extern]
+pub fn get_tree_stats(input: String) -> ExternResult<Tree<TreeStats>> {
+    let resolved = resolve_path(input)?;
+    let mut tree = Tree::new(TreeStats::default());
+    build_stats_tree(&mut tree, 0, tree_path(resolved.path))?;
+    Ok(tree)
+}
 
 #[derive(Clone, Serialize, Deserialize, Debug, Default)]
 pub struct Tree<T> 
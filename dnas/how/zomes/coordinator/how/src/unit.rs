@@ -1,15 +1,16 @@
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub use hdk::prelude::*;
 use holo_hash::{EntryHashB64};
 use how_integrity::Document;
 use how_integrity::{Unit, EntryTypes, LinkTypes};
 
-use crate::document::{update_document, UpdateDocumentInput, _update_document};
+use crate::document::{get_entry_details, update_document, UpdateDocumentInput, update_document_revision, SECTION_TYPE_PROCESS};
+use crate::template::{append_sections, process_path_for_state, sections_from_hierarchy};
 use crate::error::*;
 //use crate::signals::*;
-use crate::tree::{UnitInfo, _get_tree, tree_path, _get_path_tree, tree_path_to_str, PathContent, Node};
+use crate::tree::{UnitInfo, _get_tree, tree_path, _get_path_tree, tree_path_to_str, resolve_path, get_entry_hashes, PathContent, Node};
 
 pub fn get_units_path() -> Path {
     Path::from("units")
@@ -17,6 +18,7 @@ pub fn get_units_path() -> Path {
 
 pub const START_STATE: &str = "define";
 pub const ALIVE_STATE: &str = "_alive";
+pub const BUILD_STATE: &str = "_build";
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -27,9 +29,41 @@ pub struct UnitInput {
 
 #[hdk_extern]
 pub fn create_unit(input: UnitInput) -> ExternResult<UnitOutput> {
+    check_parents(&input.unit, None, vec![])?;
     Ok(create_unit_inner(input.unit, &input.state)?)
 }
 
+// makes sure all of a unit's parents exist in the tree and that none of them is the unit
+// itself or one of its descendants (which would make build_tree walk in circles)
+pub fn check_parents(unit: &Unit, hash: Option<&EntryHash>, old_paths: Vec<Path>) -> HowResult<()> {
+    if let Some(path) = unit.cyclic_parent() {
+        return Err(HowError::InvalidParent { path, reason: String::from("the unit would be its own ancestor") });
+    }
+    let mut descendants: HashSet<EntryHash> = HashSet::new();
+    if let Some(hash) = hash {
+        descendants.insert(hash.clone());
+        for path in old_paths {
+            let sub_tree = _get_path_tree(path)?;
+            // the root of the sub tree holds the unit itself and any units sharing its path
+            for node in sub_tree.tree.into_iter().skip(1) {
+                for info in node.val.units {
+                    descendants.insert(info.hash);
+                }
+            }
+        }
+    }
+    for parent in &unit.parents {
+        let (units, _) = get_entry_hashes(&tree_path(parent.clone()))?;
+        if units.is_empty() {
+            return Err(HowError::InvalidParent { path: parent.clone(), reason: String::from("no unit exists at this path") });
+        }
+        if units.iter().any(|info| descendants.contains(&info.hash)) {
+            return Err(HowError::InvalidParent { path: parent.clone(), reason: String::from("the path is the unit itself or one of its descendants") });
+        }
+    }
+    Ok(())
+}
+
 pub fn delete_unit_links(hash: EntryHash, tree_paths: Vec<Path>)  -> ExternResult<()> {
     let path = get_units_path();
     let anchor_hash = path.path_entry_hash()?;
@@ -80,6 +114,11 @@ pub fn create_unit_links(hash: EntryHash, tree_paths: Vec<Path>, state: &str, ve
     Ok(())
 }
 
+pub fn create_redirect(from: &Path, hash: EntryHash, to: &str) -> ExternResult<()> {
+    create_link(from.path_entry_hash()?, hash, LinkTypes::Redirect, LinkTag::new(String::from(to)))?;
+    Ok(())
+}
+
 pub fn create_unit_inner(input: Unit, state: &str) -> ExternResult<UnitOutput> {
     let action_hash = create_entry(EntryTypes::Unitx(input.clone()))?;
     let tree_paths = input.tree_paths();
@@ -118,6 +157,27 @@ fn get_units(_: ()) -> ExternResult<Vec<UnitOutput>> {
     Ok(units)
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitsAtPathOutput {
+    pub path: String,
+    pub redirected_from: Option<String>,
+    pub units: Vec<UnitOutput>,
+}
+
+/// returns the units at a path, following any redirects left by renamed or moved units
+#[hdk_extern]
+fn get_units_at_path(input: String) -> ExternResult<UnitsAtPathOutput> {
+    let resolved = resolve_path(input)?;
+    let anchor_hash = tree_path(resolved.path.clone()).path_entry_hash()?;
+    let units = get_units_inner(anchor_hash)?;
+    Ok(UnitsAtPathOutput {
+        path: resolved.path,
+        redirected_from: resolved.redirected_from,
+        units,
+    })
+}
+
 pub fn convert_tag(tag: LinkTag) -> ExternResult<(String,String,String)> {
     let tag_string = String::from_utf8(tag.into_inner())
     .map_err(|_e| wasm_error!(WasmErrorInner::Guest(String::from("could not convert link tag to string"))))?;
@@ -167,6 +227,16 @@ fn get_units_inner(base: EntryHash) -> HowResult<Vec<UnitOutput>> {
     Ok(unit_records)
 }
 
+pub fn get_unit_entry(hash: EntryHash) -> ExternResult<Unit> {
+    let record = get(hash, GetOptions::default())?
+         .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Unit not found"))))?;
+    let unit: Unit = record
+        .entry()
+        .to_app_option().map_err(|err| wasm_error!(err))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Malformed unit"))))?;
+    Ok(unit)
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AdvanceStateInput {
@@ -178,18 +248,15 @@ pub struct AdvanceStateInput {
 #[hdk_extern]
 pub fn advance_state(input: AdvanceStateInput) -> ExternResult<EntryHashB64> {
     let hash = EntryHash::from(input.unit_hash);
-    let record = get(hash.clone(), GetOptions::default())?
-         .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Unit not found"))))?;
-    let unit: Unit = record
-        .entry()
-        .to_app_option().map_err(|err| wasm_error!(err))?
-
-        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Malformed unit"))))?;
+    let unit = get_unit_entry(hash.clone())?;
     let mut document = input.document;
+    if let Some(process_path) = process_path_for_state(&unit, &input.new_state) {
+        append_sections(&mut document, sections_from_hierarchy(&process_path, 2, SECTION_TYPE_PROCESS)?);
+    }
     let now = sys_time()?.as_micros(); // we need to do this to make sure that content is distinct in case of moving state back and forth for history.
-    document.meta.insert("timestamp".to_string(),now.to_string());
+    document.meta.timestamp = Some(now);
     let new_document = UpdateDocumentInput { 
-        hash: input.document_hash.clone(), path: unit.path_str()?, document };
+        hash: input.document_hash.clone(), path: unit.path_str()?, document, expected_head: Some(input.document_hash.clone()) };
     let new_doc_hash = update_document(new_document)?;
     
     delete_unit_links(hash.clone(), unit.tree_paths())?;
@@ -230,6 +297,7 @@ pub fn update_unit(input: UpdateUnitInput) -> ExternResult<UnitOutput> {
         .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Malformed unit"))))?;
 
     let old_tree_paths = old_unit.tree_paths();
+    check_parents(&input.unit, Some(&input.hash), old_tree_paths.clone())?;
     let new_unit_output = _update_unit(input.hash.clone(), old_action_hash, old_tree_paths.clone(), &input.unit, &input.state)?;
 
     let old_path = old_unit.path_str()?;
@@ -251,10 +319,18 @@ pub fn update_unit(input: UpdateUnitInput) -> ExternResult<UnitOutput> {
 }
 
 pub fn _update_unit(hash: EntryHash, action_hash: ActionHash, paths: Vec<Path>, new_unit: &Unit, state: &str) -> ExternResult<UnitOutput> {
-    delete_unit_links(hash.clone(), paths)?;
+    delete_unit_links(hash.clone(), paths.clone())?;
     let new_action_hash = update_entry(action_hash, new_unit)?;
     let new_unit_hash = hash_entry(new_unit)?;
-    create_unit_links(new_unit_hash.clone(), new_unit.tree_paths(), state, &new_unit.version, new_unit.flags_str())?;
+    let new_paths = new_unit.tree_paths();
+    create_unit_links(new_unit_hash.clone(), new_paths.clone(), state, &new_unit.version, new_unit.flags_str())?;
+    // leave a redirect behind at any path the unit no longer lives at so old references keep working
+    let new_path = new_unit.path_str()?;
+    for path in paths {
+        if !new_paths.contains(&path) {
+            create_redirect(&path, new_unit_hash.clone(), &new_path)?;
+        }
+    }
     let maybe_record = get(new_action_hash, GetOptions::default())?;
     let record = maybe_record.ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
         "Could not get the record created just now"
@@ -299,6 +375,11 @@ pub fn reparent_document(old_unit_hash: EntryHash,  new_unit_hash: EntryHash, ne
         .ok_or(wasm_error!(WasmErrorInner::Guest(String::from("Malformed document"))))?;
 
     if document.unit_hash == old_unit_hash {
+        // older revisions move along with the head but must not become heads themselves
+        let was_head = match get_entry_details(hash.clone())? {
+            Some(details) => details.updates.is_empty(),
+            None => true,
+        };
         document.unit_hash = new_unit_hash;
         let new_path = format!("{}.{}", new_parent, new_unit.path_abbreviation);
         let path = tree_path(old_path);
@@ -319,7 +400,7 @@ pub fn reparent_document(old_unit_hash: EntryHash,  new_unit_hash: EntryHash, ne
             HDK.with(|hdk| hdk.borrow().delete_link(input))?;
         }
 
-        _update_document( record.action_address().clone().into(), new_path, &document )?;
+        update_document_revision(record.action_address().clone().into(), new_path, &document, was_head)?;
     }
     Ok(())
 }
\ No newline at end of file
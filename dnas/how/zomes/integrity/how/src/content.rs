@@ -0,0 +1,331 @@
+use hdi::prelude::*;
+use holo_hash::AgentPubKeyB64;
+use serde_json::Value;
+
+// A section's content type is a name optionally followed by `;key=value` parameters,
+// e.g. `application/json;schema=config` or `enum;values=low,medium,high`.  Types not in
+// the registry are free-form, so content types the UI invents keep working.
+
+pub const CONTENT_TYPE_MARKDOWN: &str = "text/markdown";
+pub const CONTENT_TYPE_JSON: &str = "application/json";
+pub const CONTENT_TYPE_AGENT_LIST: &str = "agent-list";
+pub const CONTENT_TYPE_AGENT_LIST_LEGACY: &str = "json/agents"; // what the UI has always used
+pub const CONTENT_TYPE_NUMBER: &str = "number";
+pub const CONTENT_TYPE_DATE: &str = "date";
+pub const CONTENT_TYPE_ENUM: &str = "enum";
+// prefix of the type of a section whose sides conflicted in a merge, followed by the
+// section's own type, e.g. `conflict:number`, it holds both sides until resolved
+pub const CONTENT_TYPE_CONFLICT: &str = "conflict:";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContentType {
+    Markdown,
+    Json { schema: Option<String> }, // name of the process section holding the schema
+    AgentList,
+    Number,
+    Date,
+    Enum { values: Vec<String> },
+    Conflict { original: String },
+    Other(String),
+}
+
+fn param<'a>(params: &[&'a str], key: &str) -> Option<&'a str> {
+    params.iter()
+        .filter_map(|p| (*p).split_once('='))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, v)| v.trim())
+}
+
+impl ContentType {
+    pub fn parse(content_type: &str) -> Self {
+        if let Some(original) = content_type.strip_prefix(CONTENT_TYPE_CONFLICT) {
+            return ContentType::Conflict { original: String::from(original) };
+        }
+        let parts: Vec<&str> = content_type.split(';').collect();
+        let params = &parts[1..];
+        match parts[0].trim() {
+            CONTENT_TYPE_MARKDOWN => ContentType::Markdown,
+            CONTENT_TYPE_JSON => ContentType::Json { schema: param(params, "schema").map(String::from) },
+            CONTENT_TYPE_AGENT_LIST | CONTENT_TYPE_AGENT_LIST_LEGACY => ContentType::AgentList,
+            CONTENT_TYPE_NUMBER => ContentType::Number,
+            CONTENT_TYPE_DATE => ContentType::Date,
+            CONTENT_TYPE_ENUM => ContentType::Enum {
+                values: param(params, "values")
+                    .map(|v| v.split(',').map(|s| String::from(s.trim())).collect())
+                    .unwrap_or_default(),
+            },
+            _ => ContentType::Other(String::from(content_type)),
+        }
+    }
+
+    // whether content of this type can hold arbitrary text, e.g. conflict markers
+    pub fn is_text(&self) -> bool {
+        matches!(self, ContentType::Markdown | ContentType::Conflict { .. } | ContentType::Other(_))
+    }
+
+    /// checks content of this type, empty content is always valid as sections start out empty
+    pub fn validate(&self, content: &str) -> Result<(), String> {
+        if content.trim().is_empty() {
+            return Ok(());
+        }
+        match self {
+            ContentType::Markdown | ContentType::Conflict { .. } | ContentType::Other(_) => Ok(()),
+            ContentType::Json { .. } => serde_json::from_str::<Value>(content)
+                .map(|_| ())
+                .map_err(|e| format!("invalid JSON: {}", e)),
+            ContentType::AgentList => {
+                let agents: Vec<String> = serde_json::from_str(content)
+                    .map_err(|_| String::from("expected a JSON array of agent keys"))?;
+                for agent in agents {
+                    AgentPubKeyB64::from_b64_str(&agent).map_err(|_| format!("invalid agent key {}", agent))?;
+                }
+                Ok(())
+            }
+            ContentType::Number => match content.trim().parse::<f64>() {
+                Ok(n) if n.is_finite() => Ok(()),
+                _ => Err(format!("{} is not a number", content.trim())),
+            },
+            ContentType::Date => validate_date(content.trim()),
+            ContentType::Enum { values } => {
+                if values.iter().any(|v| v == content.trim()) {
+                    Ok(())
+                } else {
+                    Err(format!("{} is not one of {}", content.trim(), values.join(", ")))
+                }
+            }
+        }
+    }
+
+    /// puts valid content into its canonical form, content is returned unchanged if it
+    /// doesn't validate
+    pub fn normalize(&self, content: &str) -> String {
+        if content.trim().is_empty() || self.validate(content).is_err() {
+            return String::from(content);
+        }
+        match self {
+            ContentType::Markdown => content.replace("\r\n", "\n"),
+            ContentType::Conflict { .. } | ContentType::Other(_) => String::from(content),
+            ContentType::Json { .. } => {
+                let value: Value = serde_json::from_str(content).unwrap_or(Value::Null);
+                serde_json::to_string(&value).unwrap_or_else(|_| String::from(content))
+            }
+            ContentType::AgentList => {
+                let agents: Vec<String> = serde_json::from_str(content).unwrap_or_default();
+                let mut unique: Vec<String> = vec![];
+                for agent in agents {
+                    if !unique.contains(&agent) {
+                        unique.push(agent);
+                    }
+                }
+                serde_json::to_string(&unique).unwrap_or_else(|_| String::from(content))
+            }
+            ContentType::Number | ContentType::Date | ContentType::Enum { .. } => String::from(content.trim()),
+        }
+    }
+}
+
+fn validate_date(date: &str) -> Result<(), String> {
+    let invalid = || format!("{} is not a date of the form YYYY-MM-DD", date);
+    let parts: Vec<&str> = date.split('-').collect();
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+        return Err(invalid());
+    }
+    let year: u32 = parts[0].parse().map_err(|_| invalid())?;
+    let month: u32 = parts[1].parse().map_err(|_| invalid())?;
+    let day: u32 = parts[2].parse().map_err(|_| invalid())?;
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return Err(invalid()),
+    };
+    if day == 0 || day > days {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// checks a value against the commonly used part of JSON Schema: type, enum, required,
+/// properties, items, minimum/maximum and minLength/maxLength
+pub fn check_json_schema(value: &Value, schema: &Value, at: &str) -> Result<(), String> {
+    let schema = match schema.as_object() {
+        Some(schema) => schema,
+        None => return Ok(()),
+    };
+    if let Some(expected) = schema.get("type").and_then(|t| t.as_str()) {
+        let actual = json_type(value);
+        if actual != expected && !(expected == "number" && actual == "integer") {
+            return Err(format!("{}: expected {}, found {}", at, expected, actual));
+        }
+    }
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            return Err(format!("{}: value is not one of the allowed values", at));
+        }
+    }
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64()) {
+            if n < min {
+                return Err(format!("{}: {} is less than {}", at, n, min));
+            }
+        }
+        if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64()) {
+            if n > max {
+                return Err(format!("{}: {} is more than {}", at, n, max));
+            }
+        }
+    }
+    if let Some(s) = value.as_str() {
+        let length = s.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(|m| m.as_u64()) {
+            if length < min {
+                return Err(format!("{}: shorter than {} characters", at, min));
+            }
+        }
+        if let Some(max) = schema.get("maxLength").and_then(|m| m.as_u64()) {
+            if length > max {
+                return Err(format!("{}: longer than {} characters", at, max));
+            }
+        }
+    }
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+            for key in required.iter().filter_map(|k| k.as_str()) {
+                if !object.contains_key(key) {
+                    return Err(format!("{}: missing required property {}", at, key));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+            for (key, property_schema) in properties {
+                if let Some(property) = object.get(key) {
+                    check_json_schema(property, property_schema, &format!("{}.{}", at, key))?;
+                }
+            }
+        }
+    }
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            check_json_schema(item, item_schema, &format!("{}[{}]", at, i))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn agent(byte: u8) -> String {
+        AgentPubKeyB64::from(AgentPubKey::from_raw_32(vec![byte; 32])).to_string()
+    }
+
+    #[test]
+    fn parses_content_types_and_their_parameters() {
+        assert_eq!(ContentType::parse("text/markdown"), ContentType::Markdown);
+        assert_eq!(ContentType::parse("application/json"), ContentType::Json { schema: None });
+        assert_eq!(ContentType::parse("application/json; schema = config"), ContentType::Json { schema: Some(String::from("config")) });
+        assert_eq!(ContentType::parse("json/agents"), ContentType::AgentList);
+        assert_eq!(ContentType::parse("agent-list"), ContentType::AgentList);
+        assert_eq!(ContentType::parse("enum;values=low, medium,high"), ContentType::Enum {
+            values: vec![String::from("low"), String::from("medium"), String::from("high")],
+        });
+        assert_eq!(ContentType::parse("conflict:number"), ContentType::Conflict { original: String::from("number") });
+        assert_eq!(ContentType::parse("text/plain"), ContentType::Other(String::from("text/plain")));
+    }
+
+    #[test]
+    fn empty_content_is_always_valid() {
+        for content_type in ["number", "date", "enum;values=a", "application/json", "agent-list"] {
+            assert_eq!(ContentType::parse(content_type).validate("  "), Ok(()));
+        }
+    }
+
+    #[test]
+    fn validates_content() {
+        let number = ContentType::parse("number");
+        assert!(number.validate(" 12.5 ").is_ok());
+        assert!(number.validate("twelve").is_err());
+        assert!(number.validate("inf").is_err());
+
+        let json = ContentType::parse("application/json");
+        assert!(json.validate("{\"a\": [1, 2]}").is_ok());
+        assert!(json.validate("{a: 1}").is_err());
+
+        let level = ContentType::parse("enum;values=low,high");
+        assert!(level.validate("high").is_ok());
+        assert_eq!(level.validate("medium"), Err(String::from("medium is not one of low, high")));
+
+        let agents = ContentType::parse("agent-list");
+        assert!(agents.validate(&format!("[\"{}\"]", agent(1))).is_ok());
+        assert!(agents.validate("[\"uhCAknotakey\"]").is_err());
+        assert!(agents.validate("not a list").is_err());
+
+        // anything goes in free-form and conflicting sections
+        assert!(ContentType::parse("text/plain").validate("<<<<<<< left").is_ok());
+        assert!(ContentType::parse("conflict:number").validate("<<<<<<< left").is_ok());
+    }
+
+    #[test]
+    fn validates_dates() {
+        assert!(validate_date("2024-02-29").is_ok());
+        assert!(validate_date("2000-02-29").is_ok());
+        assert!(validate_date("1900-02-29").is_err());
+        assert!(validate_date("2023-04-31").is_err());
+        assert!(validate_date("2023-13-01").is_err());
+        assert!(validate_date("2023-1-01").is_err());
+        assert!(validate_date("01/02/2023").is_err());
+    }
+
+    #[test]
+    fn normalizes_valid_content() {
+        assert_eq!(ContentType::parse("application/json").normalize("{ \"a\" : 1 }"), "{\"a\":1}");
+        assert_eq!(ContentType::parse("number").normalize(" 12 "), "12");
+        assert_eq!(ContentType::parse("text/markdown").normalize("a\r\nb"), "a\nb");
+        let (a, b) = (agent(1), agent(2));
+        assert_eq!(
+            ContentType::parse("json/agents").normalize(&format!("[\"{}\", \"{}\", \"{}\"]", a, b, a)),
+            format!("[\"{}\",\"{}\"]", a, b),
+        );
+        // invalid content is left for validation to reject
+        assert_eq!(ContentType::parse("number").normalize(" twelve "), " twelve ");
+        assert_eq!(ContentType::parse("text/plain").normalize(" as is\r\n"), " as is\r\n");
+    }
+
+    #[test]
+    fn checks_json_against_a_schema() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": {"type": "string", "minLength": 2},
+                "size": {"type": "number", "minimum": 0, "maximum": 10},
+                "tags": {"type": "array", "items": {"enum": ["a", "b"]}},
+            },
+        });
+        assert_eq!(check_json_schema(&json!({"name": "ok", "size": 3, "tags": ["a"]}), &schema, "$"), Ok(()));
+        assert_eq!(check_json_schema(&json!({"size": 3}), &schema, "$"), Err(String::from("$: missing required property name")));
+        assert_eq!(check_json_schema(&json!({"name": "x"}), &schema, "$"), Err(String::from("$.name: shorter than 2 characters")));
+        assert_eq!(check_json_schema(&json!({"name": "ok", "size": 11}), &schema, "$"), Err(String::from("$.size: 11 is more than 10")));
+        assert_eq!(check_json_schema(&json!({"name": "ok", "tags": ["c"]}), &schema, "$"), Err(String::from("$.tags[0]: value is not one of the allowed values")));
+        assert_eq!(check_json_schema(&json!([]), &schema, "$"), Err(String::from("$: expected object, found array")));
+        // integers are numbers too
+        assert_eq!(check_json_schema(&json!(2.5), &json!({"type": "number"}), "$"), Ok(()));
+        assert_eq!(check_json_schema(&json!(2), &json!({"type": "number"}), "$"), Ok(()));
+    }
+}
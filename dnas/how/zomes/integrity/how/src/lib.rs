@@ -3,6 +3,13 @@ use hdk::hash_path::path::{Component, Path};
 use std::collections::BTreeMap;
 use holo_hash::{EntryHashB64, AgentPubKeyB64};
 
+pub mod meta;
+pub use meta::*;
+pub mod requirement;
+pub use requirement::*;
+pub mod content;
+pub use content::*;
+
 
 type ProcessType = String;
 type ProcessName = String;
@@ -19,12 +26,12 @@ pub struct Unit {
     pub stewards: Vec<AgentPubKeyB64>,  // people who can change this document
     pub processes: Vec<(ProcessType,ProcessName)>, // paths to processes to use
     pub history: BTreeMap<String, EntryHashB64>,
-    pub meta: BTreeMap<String, String>, // for UI to do things    pub name: String,
+    pub meta: UnitMeta, // for UI to do things
 }
 
 impl Unit {
     pub fn flags_str(&self) -> &str {
-        match self.meta.get("flags") {
+        match &self.meta.flags {
             Some(flags) => flags,
             None => "",
         }
@@ -43,6 +50,21 @@ impl Unit {
         }
         Ok(seg.join("."))
     }
+    // returns the first parent that would make this unit its own ancestor, i.e. a parent
+    // that is one of the unit's own paths or lies somewhere underneath one of them
+    pub fn cyclic_parent(&self) -> Option<String> {
+        let own_paths: Vec<String> = self.parents.iter()
+            .map(|p| format!("{}.{}", p, self.path_abbreviation))
+            .collect();
+        for parent in &self.parents {
+            for own in &own_paths {
+                if parent == own || parent.starts_with(&format!("{}.", own)) {
+                    return Some(parent.clone())
+                }
+            }
+        }
+        None
+    }
     pub fn tree_paths(&self) -> Vec<Path> {
         let mut paths = Vec::new();
         for parent in &self.parents {
@@ -67,6 +89,9 @@ impl Unit {
 }
 
 
+// sections of any other type are definitions for the documents that inherit them
+pub const SECTION_TYPE_CONTENT: &str = "c";
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Section {
@@ -99,7 +124,7 @@ pub struct Document {
     pub state: String,         // name of current process
     pub editors: Vec<AgentPubKeyB64>, // people who can change this document, if empty anyone can
     pub content: Vec<Section>, // semantically identified content components
-    pub meta: BTreeMap<String, String>, // semantically identified meta data including state
+    pub meta: DocumentMeta, // semantically identified meta data including state
 }
 
 
@@ -118,10 +143,78 @@ pub enum LinkTypes {
     Unit,
     Tree,
     Mark,
+    Redirect,
+    Head,
+    Comment,
+    Reply,
+    Provenance,
 }
 
 #[hdk_extern]
-pub fn validate(_op: Op) -> ExternResult<ValidateCallbackResult> {
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op.flattened::<EntryTypes, LinkTypes>()? {
+        FlatOp::StoreEntry(OpEntry::CreateEntry { app_entry, .. })
+        | FlatOp::StoreEntry(OpEntry::UpdateEntry { app_entry, .. }) => validate_entry(app_entry),
+        FlatOp::StoreRecord(OpRecord::CreateEntry { app_entry, .. })
+        | FlatOp::StoreRecord(OpRecord::UpdateEntry { app_entry, .. }) => validate_entry(app_entry),
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+fn validate_entry(entry: EntryTypes) -> ExternResult<ValidateCallbackResult> {
+    match entry {
+        EntryTypes::Unitx(unit) => validate_unit(&unit),
+        EntryTypes::Document(document) => validate_document(&document),
+    }
+}
+
+fn validate_document(document: &Document) -> ExternResult<ValidateCallbackResult> {
+    // only content sections hold content of their type, the other section types hold
+    // definitions for the documents that inherit them
+    for section in document.content.iter().filter(|s| s.section_type == SECTION_TYPE_CONTENT) {
+        if let Err(reason) = ContentType::parse(&section.content_type).validate(&section.content) {
+            return Ok(ValidateCallbackResult::Invalid(format!("section {}: {}", section.name, reason)));
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_unit(unit: &Unit) -> ExternResult<ValidateCallbackResult> {
+    // whether parents exist can't be checked deterministically here, so that is left to
+    // the coordinator, but a parent underneath the unit itself is always invalid
+    if let Some(parent) = unit.cyclic_parent() {
+        return Ok(ValidateCallbackResult::Invalid(format!("parent {} would make the unit its own ancestor", parent)));
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(parents: &[&str], path_abbreviation: &str) -> Unit {
+        Unit {
+            parents: parents.iter().map(|p| String::from(*p)).collect(),
+            version: String::from("vidx:1"),
+            short_name: String::from(path_abbreviation),
+            path_abbreviation: String::from(path_abbreviation),
+            stewards: vec![],
+            processes: vec![],
+            history: BTreeMap::new(),
+            meta: UnitMeta::default(),
+        }
+    }
+
+    #[test]
+    fn cyclic_parent() {
+        assert_eq!(unit(&[], "a").cyclic_parent(), None);
+        assert_eq!(unit(&["a"], "b").cyclic_parent(), None);
+        assert_eq!(unit(&["a", "x.y"], "b").cyclic_parent(), None);
+        // a second parent that is the unit's path under its first parent
+        assert_eq!(unit(&["a", "a.b"], "b").cyclic_parent(), Some(String::from("a.b")));
+        assert_eq!(unit(&["a", "a.b.c"], "b").cyclic_parent(), Some(String::from("a.b.c")));
+        // siblings that merely start with the same letters aren't underneath the unit
+        assert_eq!(unit(&["a", "a.bc"], "b").cyclic_parent(), None);
+    }
+}
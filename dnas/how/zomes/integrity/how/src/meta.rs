@@ -0,0 +1,345 @@
+use hdi::prelude::*;
+use std::collections::BTreeMap;
+
+// Meta data is stored as a string map, just as it was before it was typed, so entries
+// written by older versions (and by the UI) still load.  Keys we know about are lifted
+// into typed fields, but only when converting them back gives exactly the same string,
+// otherwise re-hashing a loaded entry would no longer match its entry hash.  Anything
+// else stays in `extra`.
+
+/// version of the meta schema written by this code, entries without one are version 0
+pub const META_SCHEMA_VERSION: u32 = 2;
+
+// lists are JSON arrays from this version on, before they were joined with commas, which
+// neither holds values with commas nor tells an empty list from a list of one ""
+const JSON_LISTS_VERSION: u32 = 2;
+
+const SCHEMA_VERSION_KEY: &str = "schemaVersion";
+
+fn take_string(map: &mut BTreeMap<String, String>, key: &str) -> Option<String> {
+    map.remove(key)
+}
+
+// only takes the value if it round trips, so "007" stays an extension value
+fn take_parsed<T: std::str::FromStr + ToString>(map: &mut BTreeMap<String, String>, key: &str) -> Option<T> {
+    let value = map.get(key)?;
+    let parsed: T = value.parse().ok()?;
+    if parsed.to_string() != *value {
+        return None;
+    }
+    map.remove(key);
+    Some(parsed)
+}
+
+fn take_list(map: &mut BTreeMap<String, String>, key: &str, version: u32) -> Option<Vec<String>> {
+    if version < JSON_LISTS_VERSION {
+        return map.remove(key).map(|v| v.split(',').map(String::from).collect());
+    }
+    let value = map.get(key)?;
+    let list: Vec<String> = serde_json::from_str(value).ok()?;
+    if serde_json::to_string(&list).ok()? != *value {
+        return None;
+    }
+    map.remove(key);
+    Some(list)
+}
+
+fn put_string(map: &mut BTreeMap<String, String>, key: &str, value: Option<String>) {
+    if let Some(value) = value {
+        map.insert(String::from(key), value);
+    }
+}
+
+fn put_list(map: &mut BTreeMap<String, String>, key: &str, value: Option<Vec<String>>, version: u32) {
+    let value = if version < JSON_LISTS_VERSION {
+        value.map(|v| v.join(","))
+    } else {
+        value.and_then(|v| serde_json::to_string(&v).ok())
+    };
+    put_string(map, key, value);
+}
+
+fn take_schema_version(map: &mut BTreeMap<String, String>) -> u32 {
+    take_parsed(map, SCHEMA_VERSION_KEY).unwrap_or(0)
+}
+
+fn put_schema_version(map: &mut BTreeMap<String, String>, version: u32) {
+    if version != 0 {
+        map.insert(String::from(SCHEMA_VERSION_KEY), version.to_string());
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<String, String>", into = "BTreeMap<String, String>")]
+pub struct UnitMeta {
+    pub schema_version: u32,
+    pub flags: Option<String>,
+    pub extra: BTreeMap<String, String>, // for UI to do things
+}
+
+impl UnitMeta {
+    pub fn new() -> Self {
+        UnitMeta {
+            schema_version: META_SCHEMA_VERSION,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<BTreeMap<String, String>> for UnitMeta {
+    fn from(mut map: BTreeMap<String, String>) -> Self {
+        UnitMeta {
+            schema_version: take_schema_version(&mut map),
+            flags: take_string(&mut map, "flags"),
+            extra: map,
+        }
+    }
+}
+
+impl From<UnitMeta> for BTreeMap<String, String> {
+    fn from(meta: UnitMeta) -> Self {
+        let mut map = meta.extra;
+        put_schema_version(&mut map, meta.schema_version);
+        put_string(&mut map, "flags", meta.flags);
+        map
+    }
+}
+
+/// Where a comment document points: the document, section and range being commented
+/// on, with the quoted text and its context if the comment was made with one.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentMeta {
+    pub document: String,
+    pub section: String,
+    pub start_offset: usize,
+    pub end_offset: usize,
+    pub quote: Option<String>,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<String, String>", into = "BTreeMap<String, String>")]
+pub struct DocumentMeta {
+    pub schema_version: u32,
+    pub timestamp: Option<i64>, // set when the state changes so content is distinct
+    pub comment: Option<CommentMeta>,
+    pub applied_comments: Option<Vec<String>>,
+    pub merge_conflicts: Option<Vec<String>>,
+    pub templates: BTreeMap<String, String>, // section name to the template revision it was taken from
+    pub extra: BTreeMap<String, String>,
+}
+
+impl DocumentMeta {
+    pub fn new() -> Self {
+        DocumentMeta {
+            schema_version: META_SCHEMA_VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// moves meta loaded from an entry of an older schema to the current one, so that the
+    /// lists set on it afterwards are written in the current format
+    pub fn upgrade(&mut self) {
+        self.schema_version = META_SCHEMA_VERSION;
+    }
+}
+
+const TEMPLATE_KEY_PREFIX: &str = "template:";
+
+fn take_templates(map: &mut BTreeMap<String, String>) -> BTreeMap<String, String> {
+    let keys: Vec<String> = map.keys().filter(|k| k.starts_with(TEMPLATE_KEY_PREFIX)).cloned().collect();
+    let mut templates = BTreeMap::new();
+    for key in keys {
+        if let Some(value) = map.remove(&key) {
+            templates.insert(String::from(&key[TEMPLATE_KEY_PREFIX.len()..]), value);
+        }
+    }
+    templates
+}
+
+fn take_comment(map: &mut BTreeMap<String, String>) -> Option<CommentMeta> {
+    // a comment anchor is all or nothing, a partial one stays as extension values
+    if !map.contains_key("document") || !map.contains_key("section") {
+        return None;
+    }
+    let mut attempt = map.clone();
+    let start_offset = take_parsed(&mut attempt, "startOffset")?;
+    let end_offset = take_parsed(&mut attempt, "endOffset")?;
+    let comment = CommentMeta {
+        document: take_string(&mut attempt, "document")?,
+        section: take_string(&mut attempt, "section")?,
+        start_offset,
+        end_offset,
+        quote: take_string(&mut attempt, "quote"),
+        prefix: take_string(&mut attempt, "prefix"),
+        suffix: take_string(&mut attempt, "suffix"),
+    };
+    *map = attempt;
+    Some(comment)
+}
+
+impl From<BTreeMap<String, String>> for DocumentMeta {
+    fn from(mut map: BTreeMap<String, String>) -> Self {
+        let schema_version = take_schema_version(&mut map);
+        DocumentMeta {
+            schema_version,
+            timestamp: take_parsed(&mut map, "timestamp"),
+            comment: take_comment(&mut map),
+            applied_comments: take_list(&mut map, "appliedComments", schema_version),
+            merge_conflicts: take_list(&mut map, "mergeConflicts", schema_version),
+            templates: take_templates(&mut map),
+            extra: map,
+        }
+    }
+}
+
+impl From<DocumentMeta> for BTreeMap<String, String> {
+    fn from(meta: DocumentMeta) -> Self {
+        let mut map = meta.extra;
+        put_schema_version(&mut map, meta.schema_version);
+        put_string(&mut map, "timestamp", meta.timestamp.map(|t| t.to_string()));
+        if let Some(comment) = meta.comment {
+            map.insert(String::from("document"), comment.document);
+            map.insert(String::from("section"), comment.section);
+            map.insert(String::from("startOffset"), comment.start_offset.to_string());
+            map.insert(String::from("endOffset"), comment.end_offset.to_string());
+            put_string(&mut map, "quote", comment.quote);
+            put_string(&mut map, "prefix", comment.prefix);
+            put_string(&mut map, "suffix", comment.suffix);
+        }
+        put_list(&mut map, "appliedComments", meta.applied_comments, meta.schema_version);
+        put_list(&mut map, "mergeConflicts", meta.merge_conflicts, meta.schema_version);
+        for (section, revision) in meta.templates {
+            map.insert(format!("{}{}", TEMPLATE_KEY_PREFIX, section), revision);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (String::from(*k), String::from(*v))).collect()
+    }
+
+    fn round_trip(legacy: BTreeMap<String, String>) -> BTreeMap<String, String> {
+        DocumentMeta::from(legacy).into()
+    }
+
+    #[test]
+    fn legacy_document_meta_round_trips() {
+        let legacy = map(&[
+            ("timestamp", "1660000000000000"),
+            ("appliedComments", "uhCEkA,uhCEkB"),
+            ("template:summary", "uhCEkT"),
+            ("custom", "kept"),
+        ]);
+        let meta = DocumentMeta::from(legacy.clone());
+        assert_eq!(meta.schema_version, 0);
+        assert_eq!(meta.timestamp, Some(1660000000000000));
+        assert_eq!(meta.applied_comments, Some(vec![String::from("uhCEkA"), String::from("uhCEkB")]));
+        assert_eq!(meta.templates.get("summary"), Some(&String::from("uhCEkT")));
+        assert_eq!(meta.extra, map(&[("custom", "kept")]));
+        assert_eq!(round_trip(legacy.clone()), legacy);
+    }
+
+    #[test]
+    fn values_that_would_not_round_trip_stay_extra() {
+        // "007" would be written back as "7", and a partial comment anchor can't be typed
+        let legacy = map(&[("timestamp", "007"), ("document", "uhCEkD"), ("startOffset", "3")]);
+        let meta = DocumentMeta::from(legacy.clone());
+        assert_eq!(meta.timestamp, None);
+        assert_eq!(meta.comment, None);
+        assert_eq!(meta.extra.len(), 3);
+        assert_eq!(round_trip(legacy.clone()), legacy);
+        // a comment whose offsets aren't numbers stays as it is too
+        let legacy = map(&[("document", "uhCEkD"), ("section", "summary"), ("startOffset", "x"), ("endOffset", "4")]);
+        assert_eq!(DocumentMeta::from(legacy.clone()).comment, None);
+        assert_eq!(round_trip(legacy.clone()), legacy);
+    }
+
+    #[test]
+    fn comment_meta_round_trips() {
+        let legacy = map(&[
+            ("document", "uhCEkD"),
+            ("section", "summary"),
+            ("startOffset", "3"),
+            ("endOffset", "8"),
+            ("quote", "quick"),
+        ]);
+        let meta = DocumentMeta::from(legacy.clone());
+        assert_eq!(meta.comment, Some(CommentMeta {
+            document: String::from("uhCEkD"),
+            section: String::from("summary"),
+            start_offset: 3,
+            end_offset: 8,
+            quote: Some(String::from("quick")),
+            prefix: None,
+            suffix: None,
+        }));
+        assert!(meta.extra.is_empty());
+        assert_eq!(round_trip(legacy.clone()), legacy);
+    }
+
+    #[test]
+    fn new_meta_records_the_schema_version() {
+        let mut meta = DocumentMeta::new();
+        meta.merge_conflicts = Some(vec![String::from("summary")]);
+        let stored: BTreeMap<String, String> = meta.clone().into();
+        assert_eq!(stored, map(&[("schemaVersion", "2"), ("mergeConflicts", "[\"summary\"]")]));
+        assert_eq!(DocumentMeta::from(stored), meta);
+        let stored: BTreeMap<String, String> = UnitMeta::new().into();
+        assert_eq!(stored, map(&[("schemaVersion", "2")]));
+    }
+
+    #[test]
+    fn lists_round_trip() {
+        let lists = [vec![], vec![String::from("a,b"), String::from("")], vec![String::from("[\"c\"]")]];
+        for list in lists {
+            let mut meta = DocumentMeta::new();
+            meta.applied_comments = Some(list.clone());
+            meta.merge_conflicts = Some(list);
+            let stored: BTreeMap<String, String> = meta.clone().into();
+            assert_eq!(DocumentMeta::from(stored), meta);
+        }
+        let mut meta = DocumentMeta::new();
+        meta.merge_conflicts = Some(vec![]);
+        let stored: BTreeMap<String, String> = meta.into();
+        assert_eq!(stored.get("mergeConflicts"), Some(&String::from("[]")));
+    }
+
+    #[test]
+    fn upgraded_meta_writes_lists_as_json() {
+        let mut meta = DocumentMeta::from(map(&[("appliedComments", "uhCEkA,uhCEkB")]));
+        meta.upgrade();
+        let stored: BTreeMap<String, String> = meta.into();
+        assert_eq!(stored, map(&[("schemaVersion", "2"), ("appliedComments", "[\"uhCEkA\",\"uhCEkB\"]")]));
+        // a list that isn't JSON where it should be stays as it is
+        let stored = map(&[("schemaVersion", "2"), ("mergeConflicts", "a,b")]);
+        assert_eq!(DocumentMeta::from(stored.clone()).merge_conflicts, None);
+        assert_eq!(round_trip(stored.clone()), stored);
+    }
+
+    #[test]
+    fn unit_meta_round_trips() {
+        let legacy = map(&[("flags", "p"), ("color", "red")]);
+        let meta = UnitMeta::from(legacy.clone());
+        assert_eq!(meta.flags, Some(String::from("p")));
+        assert_eq!(meta.extra, map(&[("color", "red")]));
+        let stored: BTreeMap<String, String> = meta.into();
+        assert_eq!(stored, legacy);
+    }
+
+    #[test]
+    fn meta_serializes_as_a_string_map() {
+        let legacy = map(&[("flags", "p")]);
+        let meta: UnitMeta = serde_json::from_value(serde_json::to_value(&legacy).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&meta).unwrap(), serde_json::json!({"flags": "p"}));
+        let meta: DocumentMeta = serde_json::from_str("{}").unwrap();
+        assert_eq!(meta, DocumentMeta::default());
+    }
+}
@@ -0,0 +1,64 @@
+use hdi::prelude::*;
+use holo_hash::AgentPubKeyB64;
+
+use crate::Section;
+
+// the order documents move through their states, used to decide whether a document has
+// reached the state by which a section must be filled in
+pub const STATE_ORDER: [&str; 5] = ["_build", "define", "refine", "align", "_alive"];
+
+/// What a requirement section asks of the documents that inherit it.  It is stored as JSON
+/// in the content of the requirement section, whose name is the name of the required section.
+/// Older requirement sections only have a description, and free-form content is taken as
+/// the description.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequirementDef {
+    #[serde(skip)]
+    pub section: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>, // defaults to the content type of the requirement section
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>, // in characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filled_in_state: Option<String>, // the section may be empty until the document reaches this state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editors: Option<Vec<AgentPubKeyB64>>, // agents eligible to fill in the section
+}
+
+impl RequirementDef {
+    pub fn from_section(section: &Section) -> Self {
+        let mut def = serde_json::from_str::<RequirementDef>(&section.content).unwrap_or_else(|_| RequirementDef {
+            description: section.content.clone(),
+            ..Default::default()
+        });
+        def.section = section.name.clone();
+        if def.content_type.is_none() {
+            def.content_type = Some(section.content_type.clone());
+        }
+        def
+    }
+
+    pub fn to_content(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    // whether a document in state must have the section filled in
+    pub fn must_be_filled(&self, state: &str) -> bool {
+        match &self.filled_in_state {
+            None => false,
+            Some(required) => {
+                let reached = STATE_ORDER.iter().position(|s| *s == state);
+                let needed = STATE_ORDER.iter().position(|s| s == required);
+                match (reached, needed) {
+                    (Some(reached), Some(needed)) => reached >= needed,
+                    _ => state == required,
+                }
+            }
+        }
+    }
+}
@@ -12,11 +12,74 @@ const dnaPath = path.join(__dirname, "../../apps/launcher/dnas/how/how.dna")
 
 import * as _ from 'lodash'
 import { Base64 } from "js-base64";
+import { readFileSync } from "fs";
 
 function encodeHashToBase64(hash: Uint8Array): string {
   return `u${Base64.fromUint8Array(hash, true)}`;
 }
 
+const dnas: DnaSource[] = [{ path: dnaPath }];
+
+function how(cell: any, fn_name: string, payload: any = null): Promise<any> {
+  return cell.callZome({zome_name: 'how', fn_name, payload})
+}
+
+// players sharing a how network, each with its cell and agent key
+async function setupPlayers(scenario: Scenario, count: number = 1) {
+  const players = await scenario.addPlayersWithHapps(Array(count).fill(dnas));
+  await scenario.shareAllAgents();
+  return players.map((p) => ({cell: p.cells[0], agentKey: encodeHashToBase64(p.agentPubKey)}))
+}
+
+// retries check until it holds, for data other agents wrote to reach the DHT
+async function eventually(check: () => Promise<boolean>, tries: number = 20) {
+  for (let i = 0; i < tries; i++) {
+    if (await check()) {
+      return true
+    }
+    await pause(500)
+  }
+  return false
+}
+
+function makeUnit(parent: string | null, pathAbbreviation: string, stewards: Array<string> = []) {
+  return {
+    parents: parent === null ? [] : [parent],
+    shortName: pathAbbreviation,
+    version: "vidx:1",
+    pathAbbreviation,
+    stewards,
+    processes: [["soc_proto.process.define","declaration"]],
+    history: {},
+    meta: {},
+  }
+}
+
+function makeSection(name: string, content: string, contentType: string = "text/plain", sectionType: string = "c") {
+  return {name, content, sourcePath: "_manual", sectionType, contentType}
+}
+
+function makeDocument(unitHash: Uint8Array, content: Array<any>, documentType: string = "_document") {
+  return {unitHash, documentType, content, state: "define", editors: [], meta: {}}
+}
+
+// creates a unit at parent.pathAbbreviation with a document holding sections
+async function createUnitWithDocument(cell: any, parent: string | null, pathAbbreviation: string, sections: Array<any>, stewards: Array<string> = []) {
+  const unit = makeUnit(parent, pathAbbreviation, stewards)
+  const output = await how(cell, 'create_unit', {state: "define", unit})
+  const path = parent ? `${parent}.${pathAbbreviation}` : pathAbbreviation
+  const documentHash: string = await how(cell, 'create_document', {path, document: makeDocument(output.info.hash, sections)})
+  return {unit, unitHash: output.info.hash as Uint8Array, path, documentHash}
+}
+
+// creates the process every unit made by makeUnit follows, with a document holding sections
+async function createProcess(cell: any, sections: Array<any>) {
+  await how(cell, 'create_unit', {state: "define", unit: makeUnit(null, "soc_proto")})
+  await how(cell, 'create_unit', {state: "define", unit: makeUnit("soc_proto", "process")})
+  await how(cell, 'create_unit', {state: "define", unit: makeUnit("soc_proto.process", "define")})
+  return createUnitWithDocument(cell, "soc_proto.process.define", "declaration", sections)
+}
+
 test("how basic tests", async (t) => {
   await runScenario(async (scenario: Scenario) => {
 try {
@@ -71,7 +134,7 @@ try {
       console.log("Error in initialize", e)
     }
 
-    let docs:any = await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:""} );
+    let docs:any = (await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:""} ) as any).documents;
     t.equal(docs[0].updatedBy.length, 0)
     t.equal(docs.length, 1)
 
@@ -110,7 +173,7 @@ try {
     t.ok(document1Hash)
     console.log("document1Hash", document1Hash);
 
-    docs = await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload: doc1Path} );
+    docs = (await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:doc1Path} ) as any).documents;
     t.equal(docs.length, 1)
     t.equal(docs[0].hash, document1Hash)
 
@@ -138,7 +201,7 @@ try {
     t.equal(newDocHash, encodeHashToBase64(node.documents[1]))
     } catch(e) {console.log("error in get_tree", e)}
 
-    docs = await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:doc1Path} );
+    docs = (await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:doc1Path} ) as any).documents;
     console.log("DOCS:", docs)
     t.equal(docs[0].updatedBy.length, 1)
     t.equal(encodeHashToBase64(docs[0].updatedBy[0]), newDocHash)
@@ -146,7 +209,7 @@ try {
 
     t.equal(docs[1].marks.length, 0)
     const markActionHashes = await alice_how.callZome({zome_name:'how', fn_name:'mark_document', payload: [{hash: newDocHash, markType: 1, mark:"good"}]} );
-    docs = await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:doc1Path} );
+    docs = (await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:doc1Path} ) as any).documents;
     console.log("MARKS:", docs[1].marks)
 
     t.deepEqual(docs[1].marks[0], {markType: 1, mark:"good", author:aliceAgentKey})
@@ -161,10 +224,10 @@ try {
 
     } catch(e) {console.log("error in advance_state", e)}
 
-    docs = await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:doc1Path} );
+    docs = (await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:doc1Path} ) as any).documents;
 
     const deleteActionHash : ActionHash = await alice_how.callZome({zome_name:'how', fn_name:'delete_document', payload: docs[2].actions[0].hash} );
-    docs = await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:doc1Path} );
+    docs = (await alice_how.callZome({zome_name:'how',fn_name:'get_documents', payload:doc1Path} ) as any).documents;
     console.log("DOCS DELETED:", docs)
     t.equal(encodeHashToBase64(docs[2].deletedBy[0]),encodeHashToBase64(deleteActionHash))
   } catch (e) {
@@ -175,6 +238,61 @@ try {
 })
 
 
+// an exported tree without what differs between agents initializing the same tree:
+// hashes, authorship and the agent's key wherever it is a steward or editor
+function treeShape(bundle: any, agentKey: string) {
+  const omitted = ["hash", "unitHash", "sourceUnit", "previous", "author", "timestamp"]
+  const json = JSON.stringify(bundle, (key, value) => omitted.includes(key) ? undefined : value)
+  const shape = JSON.parse(json.split(agentKey).join("PROGENITOR"))
+  shape.units = _.sortBy(shape.units, (u: any) => [...u.unit.parents, u.unit.pathAbbreviation].join("."))
+  shape.documents = _.sortBy(shape.documents, (d: any) => [d.paths.join(","), d.document.documentType].join(":"))
+  return shape
+}
+
+test("presets build the trees the UI used to build", async (t) => {
+  // the fixtures are the Initialization the UI built, with the progenitor's key left out
+  for (const name of ["simple", "holochain"]) {
+    const shapes: Array<any> = []
+    for (const usePreset of [true, false]) {
+      await runScenario(async (scenario: Scenario) => {
+        const dnas: DnaSource[] = [{ path: dnaPath }];
+        const [alice] = await scenario.addPlayersWithHapps([dnas]);
+        const [alice_how] = alice.cells;
+        const aliceAgentKey = encodeHashToBase64(alice.agentPubKey);
+
+        if (usePreset) {
+          const report: any = await alice_how.callZome({zome_name:'how', fn_name:'initialize_with_preset', payload: name});
+          t.ok(report.createdUnits.length > 0)
+          t.equal(report.skipped.length, 0)
+        } else {
+          const fixture = readFileSync(path.join(__dirname, "fixtures", `${name}.json`), "utf8")
+          const init = JSON.parse(fixture.split("PROGENITOR").join(aliceAgentKey))
+          await alice_how.callZome({zome_name:'how', fn_name:'initialize', payload: init});
+        }
+        const bundle = await alice_how.callZome({zome_name:'how', fn_name:'export_tree', payload: ""});
+        shapes.push(treeShape(bundle, aliceAgentKey))
+
+        if (usePreset) {
+          // running it again finds everything in place
+          const again: any = await alice_how.callZome({zome_name:'how', fn_name:'initialize_with_preset', payload: name});
+          t.equal(again.createdUnits.length, 0)
+          t.equal(again.createdDocuments.length, 0)
+        }
+      })
+    }
+    t.deepEqual(shapes[0], shapes[1], `the ${name} preset`)
+  }
+
+  await runScenario(async (scenario: Scenario) => {
+    const dnas: DnaSource[] = [{ path: dnaPath }];
+    const [alice] = await scenario.addPlayersWithHapps([dnas]);
+    const [alice_how] = alice.cells;
+    t.deepEqual(await alice_how.callZome({zome_name:'how', fn_name:'list_presets', payload: null}), ["simple", "holochain"])
+    await t.rejects(alice_how.callZome({zome_name:'how', fn_name:'initialize_with_preset', payload: "nonesuch"}))
+  })
+})
+
+
 type RustNode = {
   idx: number,
   val: any,
@@ -192,4 +310,916 @@ function buildTree(tree: Array<RustNode>, node: RustNode): Node {
     t.children.push(buildTree(tree, tree[n]))
   }
   return t
-}
\ No newline at end of file
+}
+
+test("renamed units leave redirects at their old paths", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    await how(alice.cell, 'create_unit', {state: "_alive", unit: makeUnit(null, "a")})
+    const {unit, unitHash} = await createUnitWithDocument(alice.cell, "a", "b", [makeSection("title", "B")])
+
+    const renamed = await how(alice.cell, 'update_unit', {hash: unitHash, state: "define", unit: {...unit, pathAbbreviation: "c"}})
+
+    const units = await how(alice.cell, 'get_units_at_path', "a.b")
+    t.equal(units.path, "a.c")
+    t.equal(units.redirectedFrom, "a.b")
+    t.equal(units.units.length, 1)
+
+    const documents = await how(alice.cell, 'get_documents', "a.b")
+    t.equal(documents.path, "a.c")
+    t.equal(documents.redirectedFrom, "a.b")
+    t.ok(documents.documents.some((d: any) => d.content.content[0].content == "B"))
+
+    // the new path itself isn't redirected
+    const current = await how(alice.cell, 'get_units_at_path', "a.c")
+    t.equal(current.redirectedFrom, null)
+
+    // renaming again chains the redirects
+    await how(alice.cell, 'update_unit', {hash: renamed.info.hash, state: "define", unit: {...unit, pathAbbreviation: "d"}})
+    const chained = await how(alice.cell, 'get_units_at_path', "a.b")
+    t.equal(chained.path, "a.d")
+    t.equal(chained.units.length, 1)
+  })
+})
+
+test("unit parents can't make a unit its own ancestor", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const a = makeUnit(null, "a")
+    const aOutput = await how(alice.cell, 'create_unit', {state: "_alive", unit: a})
+    await how(alice.cell, 'create_unit', {state: "_alive", unit: makeUnit("a", "b")})
+
+    // a parent underneath the unit's own path
+    await t.rejects(how(alice.cell, 'create_unit', {state: "_alive", unit: {...makeUnit("a", "c"), parents: ["a", "a.c"]}}))
+    // a parent that doesn't exist
+    await t.rejects(how(alice.cell, 'create_unit', {state: "_alive", unit: makeUnit("nowhere", "c")}))
+    // moving a unit underneath one of its own descendants
+    await t.rejects(how(alice.cell, 'update_unit', {hash: aOutput.info.hash, state: "_alive", unit: {...a, parents: ["a.b"]}}))
+
+    const units = await how(alice.cell, 'get_units_at_path', "a.b")
+    t.equal(units.units.length, 1)
+    const tree = await how(alice.cell, 'get_tree')
+    t.ok(tree.tree.length > 0)
+  })
+})
+
+test("tree stats cover each node's subtree", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice, bobbo] = await setupPlayers(scenario, 2)
+    await how(alice.cell, 'create_unit', {state: "_alive", unit: makeUnit(null, "a")})
+    const b = await createUnitWithDocument(alice.cell, "a", "b", [makeSection("title", "B")])
+    const c = await createUnitWithDocument(alice.cell, "a.b", "c", [makeSection("title", "C")])
+    // revisions of a document don't count as documents of their own
+    await how(alice.cell, 'update_document', {hash: b.documentHash, path: b.path, document: makeDocument(b.unitHash, [makeSection("title", "B2")])})
+
+    const stats = await how(alice.cell, 'get_tree_stats', "a")
+    const root = stats.tree[0].val
+    t.equal(root.path, "a")
+    t.deepEqual(root.unitsByState, {"_alive": 1, "define": 2})
+    t.equal(root.documents, 2)
+    t.equal(root.comments, 0)
+    t.equal(root.contributors, 1)
+    t.ok(root.lastActivity)
+
+    const bStats = stats.tree[stats.tree[0].children[0]].val
+    t.equal(bStats.path, "a.b")
+    t.deepEqual(bStats.unitsByState, {"define": 2})
+    t.equal(bStats.documents, 2)
+
+    // whoever wrote a revision contributed to the tree
+    t.ok(await eventually(async () => {
+      try {
+        await how(bobbo.cell, 'update_document', {hash: c.documentHash, path: c.path, document: makeDocument(c.unitHash, [makeSection("title", "C2")])})
+        return true
+      } catch (e) {
+        return false
+      }
+    }))
+    t.ok(await eventually(async () => {
+      const root = (await how(alice.cell, 'get_tree_stats', "a")).tree[0].val
+      return root.contributors == 2 && root.documents == 2
+    }))
+  })
+})
+
+test("the tree renders as DOT and Mermaid diagrams", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    await how(alice.cell, 'create_unit', {state: "_alive", unit: {...makeUnit(null, "a"), shortName: "Root \"A\""}})
+    const b = {...makeUnit("a", "b"), shortName: "Bee"}
+    const bOutput = await how(alice.cell, 'create_unit', {state: "define", unit: b})
+
+    const dot: string = await how(alice.cell, 'get_tree_diagram', {format: "Dot", root: "a"})
+    t.ok(dot.startsWith("digraph how {"))
+    t.ok(dot.includes('u0 [label="Root \\"A\\"\\n_alive"];'))
+    t.ok(dot.includes('u1 [label="Bee\\ndefine"];'))
+    t.ok(dot.includes("u0 -> u1;"))
+
+    const mermaid: string = await how(alice.cell, 'get_tree_diagram', {format: "Mermaid", root: "a"})
+    t.ok(mermaid.startsWith("graph TD\n"))
+    t.ok(mermaid.includes('u0["Root #quot;A#quot;<br/>_alive"]'))
+    t.ok(mermaid.includes("u0 --> u1"))
+
+    // the root is looked up through redirects
+    await how(alice.cell, 'update_unit', {hash: bOutput.info.hash, state: "define", unit: {...b, pathAbbreviation: "c"}})
+    const moved: string = await how(alice.cell, 'get_tree_diagram', {format: "Mermaid", root: "a.b"})
+    t.ok(moved.includes('u0["Bee<br/>define"]'))
+  })
+})
+
+test("document history lists every revision oldest first", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const {unitHash, path, documentHash} = await createUnitWithDocument(alice.cell, null, "a", [makeSection("title", "one")])
+
+    const second = await how(alice.cell, 'update_document', {hash: documentHash, path, document: makeDocument(unitHash, [makeSection("title", "two")])})
+    const third = await how(alice.cell, 'update_document', {hash: second, path, document: makeDocument(unitHash, [makeSection("title", "three")])})
+
+    // any revision gets the whole history
+    for (const hash of [documentHash, second, third]) {
+      const history = await how(alice.cell, 'get_document_history', decodeHashFromBase64(hash))
+      t.deepEqual(history.revisions.map((r: any) => r.hash), [documentHash, second, third])
+      t.deepEqual(history.branches, [])
+    }
+    const history = await how(alice.cell, 'get_document_history', decodeHashFromBase64(third))
+    t.deepEqual(history.revisions[0].previous, [])
+    t.deepEqual(history.revisions[1].previous, [documentHash])
+    t.deepEqual(history.revisions[1].updatedBy, [third])
+    t.equal(history.revisions[2].author, alice.agentKey)
+    t.ok(history.revisions[0].timestamp <= history.revisions[2].timestamp)
+
+    // building on a revision that was already updated branches the history
+    const fork = await how(alice.cell, 'update_document', {hash: second, path, document: makeDocument(unitHash, [makeSection("title", "fork")])})
+    const forked = await how(alice.cell, 'get_document_history', decodeHashFromBase64(fork))
+    t.equal(forked.revisions.length, 4)
+    t.deepEqual(forked.branches, [second])
+  })
+})
+
+test("documents diff section by section", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const {unitHash, path, documentHash} = await createUnitWithDocument(alice.cell, null, "a", [
+      makeSection("title", "A title"),
+      makeSection("summary", "first line\nsecond line\n"),
+      makeSection("background", "a long account of how this came to be written"),
+      makeSection("obsolete", "gone"),
+    ])
+    const updated = await how(alice.cell, 'update_document', {hash: documentHash, path, document: {
+      ...makeDocument(unitHash, [
+        makeSection("title", "A title"),
+        makeSection("summary", "first line\nchanged line\n", "text/markdown"),
+        makeSection("history", "a long account of how this came to be written down"),
+        makeSection("added", "new"),
+      ]),
+      editors: [alice.agentKey],
+    }})
+
+    const diff = await how(alice.cell, 'diff_documents', {fromHash: decodeHashFromBase64(documentHash), toHash: decodeHashFromBase64(updated)})
+    t.equal(diff.from, documentHash)
+    t.equal(diff.to, updated)
+    const byName = _.keyBy(diff.sections, "name")
+    t.notOk(byName["title"])
+    t.equal(byName["summary"].change, "Modified")
+    t.equal(byName["summary"].previousContentType, "text/plain")
+    t.deepEqual(byName["summary"].hunks, [
+      {op: "Equal", text: "first line\n"},
+      {op: "Delete", text: "second line\n"},
+      {op: "Insert", text: "changed line\n"},
+    ])
+    t.equal(byName["history"].change, "Renamed")
+    t.equal(byName["history"].previousName, "background")
+    t.equal(byName["added"].change, "Added")
+    t.equal(byName["obsolete"].change, "Removed")
+    t.deepEqual(diff.editorsAdded, [alice.agentKey])
+    t.deepEqual(diff.editorsRemoved, [])
+
+    const words = await how(alice.cell, 'diff_documents', {fromHash: decodeHashFromBase64(documentHash), toHash: decodeHashFromBase64(updated), granularity: "Word"})
+    const history = words.sections.find((s: any) => s.name == "history")
+    t.deepEqual(history.hunks[history.hunks.length - 1], {op: "Insert", text: " down"})
+  })
+})
+
+test("each unit has a current document unless its document forked", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    await how(alice.cell, 'create_unit', {state: "_alive", unit: makeUnit(null, "a")})
+    const {unit, unitHash, path, documentHash} = await createUnitWithDocument(alice.cell, "a", "b", [makeSection("title", "one")])
+
+    let current = await how(alice.cell, 'get_current_document', unitHash)
+    t.equal(current.type, "Head")
+    t.equal(current.content.hash, documentHash)
+
+    const second = await how(alice.cell, 'update_document', {hash: documentHash, path, document: makeDocument(unitHash, [makeSection("title", "two")])})
+    current = await how(alice.cell, 'get_current_document', unitHash)
+    t.equal(current.type, "Head")
+    t.equal(current.content.hash, second)
+
+    // another update of the first revision forks the document
+    const fork = await how(alice.cell, 'update_document', {hash: documentHash, path, document: makeDocument(unitHash, [makeSection("title", "fork")])})
+    current = await how(alice.cell, 'get_current_document', unitHash)
+    t.equal(current.type, "Conflict")
+    t.deepEqual(current.content.map((d: any) => d.hash).sort(), [second, fork].sort())
+    const heads = await how(alice.cell, 'get_document_heads', decodeHashFromBase64(documentHash))
+    t.deepEqual(heads.sort(), [second, fork].sort())
+
+    // moving the unit carries both heads along, but not the revision they replaced
+    const moved = await how(alice.cell, 'update_unit', {hash: unitHash, state: "define", unit: {...unit, pathAbbreviation: "c"}})
+    current = await how(alice.cell, 'get_current_document', moved.info.hash)
+    t.equal(current.type, "Conflict")
+    t.deepEqual(current.content.map((d: any) => d.content.content[0].content).sort(), ["fork", "two"])
+  })
+})
+
+test("updates with an expected head are rejected once it is stale", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const {unitHash, path, documentHash} = await createUnitWithDocument(alice.cell, null, "a", [makeSection("title", "one")])
+    const update = (hash: string, expectedHead: string, title: string) =>
+      how(alice.cell, 'update_document', {hash, path, expectedHead, document: makeDocument(unitHash, [makeSection("title", title)])})
+
+    const second = await update(documentHash, documentHash, "two")
+    t.ok(second)
+    // the first revision was updated in the meantime
+    await t.rejects(update(documentHash, documentHash, "stale"))
+    // the expected head is no longer the head
+    await t.rejects(update(second, documentHash, "stale"))
+    const third = await update(second, second, "three")
+
+    const heads = await how(alice.cell, 'get_document_heads', decodeHashFromBase64(documentHash))
+    t.deepEqual(heads, [third])
+  })
+})
+
+test("a renamed unit with several revisions still has a single head", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    await how(alice.cell, 'create_unit', {state: "_alive", unit: makeUnit(null, "a")})
+    const {unit, unitHash, path, documentHash} = await createUnitWithDocument(alice.cell, "a", "b", [makeSection("title", "one")])
+    const second = await how(alice.cell, 'update_document', {hash: documentHash, path, document: makeDocument(unitHash, [makeSection("title", "two")])})
+    await how(alice.cell, 'update_document', {hash: second, path, document: makeDocument(unitHash, [makeSection("title", "three")])})
+
+    // renaming copies every revision to the new unit, only the copy of the head is one
+    const moved = await how(alice.cell, 'update_unit', {hash: unitHash, state: "define", unit: {...unit, pathAbbreviation: "c"}})
+    const current = await how(alice.cell, 'get_current_document', moved.info.hash)
+    t.equal(current.type, "Head")
+    t.deepEqual(await how(alice.cell, 'get_document_heads', decodeHashFromBase64(current.content.hash)), [current.content.hash])
+
+    // so the moved document can still be advanced against its expected head
+    const advanced = await how(alice.cell, 'advance_state', {
+      newState: "align", unitHash: moved.info.hash, documentHash: current.content.hash, document: current.content.content,
+    })
+    t.deepEqual(await how(alice.cell, 'get_document_heads', decodeHashFromBase64(documentHash)), [advanced])
+  })
+})
+
+test("concurrent revisions merge section by section", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const sections = (summary: string, count: string, notes: string) => [
+      makeSection("title", "T"),
+      makeSection("summary", summary),
+      makeSection("count", count, "number"),
+      makeSection("notes", notes),
+    ]
+    const {unitHash, path, documentHash: base} = await createUnitWithDocument(alice.cell, null, "a", sections("base", "1", "n"))
+    const left = await how(alice.cell, 'update_document', {hash: base, path, document: makeDocument(unitHash, sections("left", "2", "n"))})
+    const right = await how(alice.cell, 'update_document', {hash: base, path, document: makeDocument(unitHash, sections("base", "3", "right"))})
+    const other = await createUnitWithDocument(alice.cell, null, "b", [makeSection("title", "other")])
+
+    const merge = (b: string, l: string, r: string) => how(alice.cell, 'merge_documents', {
+      base: decodeHashFromBase64(b), left: decodeHashFromBase64(l), right: decodeHashFromBase64(r),
+    })
+    // the base has to be an ancestor of both heads
+    await t.rejects(merge(other.documentHash, left, right))
+    await t.rejects(merge(left, left, right))
+
+    const merged = await merge(base, left, right)
+    t.deepEqual(merged.conflicts, ["count"])
+    const current = await how(alice.cell, 'get_current_document', unitHash)
+    t.equal(current.type, "Head")
+    t.equal(current.content.hash, merged.hash)
+    const content = _.keyBy(current.content.content.content, "name")
+    t.equal(content["summary"].content, "left")
+    t.equal(content["notes"].content, "right")
+    // the markers don't fit a number, so the section is marked as a conflict of one
+    t.equal(content["count"].contentType, "conflict:number")
+    t.equal(content["count"].content, "<<<<<<< left\n2\n=======\n3\n>>>>>>> right")
+    t.deepEqual(JSON.parse(current.content.content.meta.mergeConflicts), ["count"])
+
+    // the merge updates both heads, so the history converges
+    const heads = await how(alice.cell, 'get_document_heads', decodeHashFromBase64(base))
+    t.deepEqual(heads, [merged.hash])
+  })
+})
+
+test("comments are anchored to a range of a section", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const {unitHash, documentHash} = await createUnitWithDocument(alice.cell, null, "a", [
+      makeSection("title", "A"),
+      makeSection("summary", "The quick brown fox"),
+    ])
+    const document = decodeHashFromBase64(documentHash)
+
+    const commentHash = await how(alice.cell, 'create_comment', {document, section: "summary", start: 4, end: 9, text: "too fast?", suggestion: "slow"})
+    const comments = await how(alice.cell, 'get_comments', document)
+    t.equal(comments.length, 1)
+    const [comment] = comments
+    t.equal(comment.hash, commentHash)
+    t.equal(comment.author, alice.agentKey)
+    t.equal(comment.status, "pending")
+    t.deepEqual(_.pick(comment.comment, ["document", "section", "startOffset", "endOffset", "text", "suggestion", "orphaned"]), {
+      document: documentHash, section: "summary", startOffset: 4, endOffset: 9, text: "too fast?", suggestion: "slow", orphaned: false,
+    })
+    t.deepEqual(comment.comment.anchor, {quote: "quick", prefix: "The ", suffix: " brown fox"})
+
+    await how(alice.cell, 'mark_document', [{hash: commentHash, markType: 1, mark: "rejected"}])
+    const marked = await how(alice.cell, 'get_comments', document)
+    t.equal(marked[0].status, "rejected")
+
+    // comments don't take the place of the unit's document
+    const current = await how(alice.cell, 'get_current_document', unitHash)
+    t.equal(current.type, "Head")
+    t.equal(current.content.hash, documentHash)
+  })
+})
+
+test("comments from before there were comment links are listed with the linked ones", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const {unitHash, path, documentHash} = await createUnitWithDocument(alice.cell, null, "a", [
+      makeSection("title", "A"),
+      makeSection("summary", "The quick brown fox"),
+    ])
+    const document = decodeHashFromBase64(documentHash)
+    // the way the UI made comments before, only linked at the path of the unit
+    const legacy = await how(alice.cell, 'create_document', {path, document: {
+      ...makeDocument(unitHash, [makeSection("comment", "an old one")]),
+      documentType: "_comment",
+      meta: {document: documentHash, section: "summary", startOffset: "0", endOffset: "3"},
+    }})
+    const linked = await how(alice.cell, 'create_comment', {document, section: "summary", start: 4, end: 9, text: "a new one", suggestion: null})
+
+    const comments = await how(alice.cell, 'get_comments', document)
+    t.deepEqual(comments.map((c: any) => c.hash).sort(), [legacy, linked].sort())
+  })
+})
+
+test("replies to comments form threads", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const {documentHash} = await createUnitWithDocument(alice.cell, null, "a", [
+      makeSection("title", "A"),
+      makeSection("summary", "The quick brown fox"),
+    ])
+    const document = decodeHashFromBase64(documentHash)
+    const comment = (on: string, section: string, text: string) =>
+      how(alice.cell, 'create_comment', {document: decodeHashFromBase64(on), section, start: 0, end: 0, text, suggestion: null})
+
+    const first = await comment(documentHash, "summary", "first")
+    const reply = await comment(first, "comment", "a reply")
+    const nested = await comment(reply, "comment", "a reply to the reply")
+    const second = await comment(first, "comment", "another reply")
+    await comment(documentHash, "title", "on the title")
+
+    const thread = await how(alice.cell, 'get_comment_thread', decodeHashFromBase64(first))
+    t.equal(thread.comment.hash, first)
+    t.deepEqual(thread.replies.map((r: any) => r.comment.hash), [reply, second])
+    t.deepEqual(thread.replies[0].replies.map((r: any) => r.comment.hash), [nested])
+    t.equal(thread.replies[0].replies[0].comment.comment.text, "a reply to the reply")
+
+    // replies aren't comments on the document itself
+    const comments = await how(alice.cell, 'get_comments', document)
+    t.equal(comments.length, 2)
+
+    const summary = await how(alice.cell, 'get_thread_summary', document)
+    t.equal(summary.threads, 2)
+    t.deepEqual(summary.sections["summary"], {threads: 1, replies: 3, pending: 1, approved: 0, rejected: 0, modified: 0})
+    t.equal(summary.sections["title"].replies, 0)
+  })
+})
+
+test("approved suggestions are applied when a steward approved them", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice, bobbo] = await setupPlayers(scenario, 2)
+    const {unitHash, documentHash} = await createUnitWithDocument(alice.cell, null, "a", [
+      makeSection("title", "A"),
+      makeSection("summary", "The quick brown fox jumps"),
+    ], [alice.agentKey])
+    const document = decodeHashFromBase64(documentHash)
+    const suggest = (start: number, end: number, suggestion: string) =>
+      how(alice.cell, 'create_comment', {document, section: "summary", start, end, text: "", suggestion})
+    const mark = (player: any, hash: string, mark: string) =>
+      how(player.cell, 'mark_document', [{hash, markType: 1, mark}])
+
+    const quick = await suggest(4, 9, "slow")
+    const fox = await suggest(16, 19, "cat")
+    const brown = await suggest(10, 15, "red")
+    await mark(alice, quick, "approved")
+    // only a steward's approval counts
+    await mark(bobbo, fox, "approved")
+    // and only the steward's latest status
+    await mark(alice, brown, "approved")
+    await pause(10)
+    await mark(alice, brown, "rejected")
+
+    t.ok(await eventually(async () => {
+      const comments = await how(alice.cell, 'get_comments', document)
+      return comments.find((c: any) => c.hash == fox).status == "approved"
+    }))
+
+    const output = await how(alice.cell, 'apply_approved_comments', document)
+    t.deepEqual(output.applied, [quick])
+    t.deepEqual(output.skipped, [{hash: fox, reason: "not approved by a steward"}])
+
+    const current = await how(alice.cell, 'get_current_document', unitHash)
+    t.equal(current.content.hash, output.hash)
+    const summary = current.content.content.content.find((s: any) => s.name == "summary")
+    t.equal(summary.content, "The slow brown fox jumps")
+    t.deepEqual(JSON.parse(current.content.content.meta.appliedComments), [quick])
+
+    // nothing is left to apply on the new revision
+    const again = await how(alice.cell, 'apply_approved_comments', decodeHashFromBase64(output.hash))
+    t.equal(again.hash, null)
+  })
+})
+
+test("comments are found again in later revisions by their quoted text", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const {unitHash, path, documentHash} = await createUnitWithDocument(alice.cell, null, "a", [makeSection("summary", "The quick brown fox")])
+    const oldRev = decodeHashFromBase64(documentHash)
+    const comment = (start: number, end: number) =>
+      how(alice.cell, 'create_comment', {document: oldRev, section: "summary", start, end, text: "a comment", suggestion: null})
+    const quick = await comment(4, 9)
+    const point = await comment(10, 10) // an insertion point before "brown"
+    const fox = await comment(16, 19)
+
+    const updated = await how(alice.cell, 'update_document', {hash: documentHash, path, document: makeDocument(unitHash, [makeSection("summary", "Look, The quick brown dog")])})
+    const newRev = decodeHashFromBase64(updated)
+    const results = await how(alice.cell, 'reanchor_comments', {oldRev, newRev})
+    const byComment = _.keyBy(results, "comment")
+    t.deepEqual(_.pick(byComment[quick], ["startOffset", "endOffset", "orphaned"]), {startOffset: 10, endOffset: 15, orphaned: false})
+    t.deepEqual(_.pick(byComment[point], ["startOffset", "endOffset", "orphaned"]), {startOffset: 16, endOffset: 16, orphaned: false})
+    t.equal(byComment[fox].orphaned, true)
+
+    // the comments are listed on the new revision where they now sit
+    const comments = _.keyBy(await how(alice.cell, 'get_comments', newRev), "hash")
+    t.equal(comments[quick].comment.document, updated)
+    t.equal(comments[quick].comment.startOffset, 10)
+    t.equal(comments[fox].comment.orphaned, true)
+
+    // comments already carried over are left alone
+    t.deepEqual(await how(alice.cell, 'reanchor_comments', {oldRev, newRev}), [])
+  })
+})
+
+test("meta data written before it was typed loads unchanged", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const unit = {...makeUnit(null, "a"), meta: {flags: "p", color: "red"}}
+    const unitOutput = await how(alice.cell, 'create_unit', {state: "define", unit})
+    t.equal(unitOutput.info.flags, "p")
+
+    const legacyMeta = {timestamp: "007", custom: "kept", appliedComments: "x,y", "template:summary": "uhCEkT"}
+    const documentHash = await how(alice.cell, 'create_document', {path: "a", document: {...makeDocument(unitOutput.info.hash, [makeSection("title", "A")]), meta: legacyMeta}})
+    const document = await how(alice.cell, 'get_document', decodeHashFromBase64(documentHash))
+    t.deepEqual(document.content.meta, legacyMeta)
+    // the loaded entry hashes to the hash it was created with
+    t.equal(document.hash, documentHash)
+  })
+})
+
+test("documents are instantiated from the templates above them", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const a = await createUnitWithDocument(alice.cell, null, "a", [makeSection("title", "A")])
+    const templateHash = await how(alice.cell, 'create_document', {path: "a", document: makeDocument(a.unitHash, [
+      makeSection("title", ""),
+      makeSection("scope", "What this covers"),
+    ], "_template")})
+    const process = await createProcess(alice.cell, [makeSection("steps", "1. declare", "text/plain", "p")])
+
+    const b = await how(alice.cell, 'create_unit', {state: "define", unit: {...makeUnit("a", "b", [alice.agentKey]), shortName: "Bee"}})
+    const documentHash = await how(alice.cell, 'instantiate_template', {unitHash: b.info.hash, templatePath: "a"})
+    const document = (await how(alice.cell, 'get_document', decodeHashFromBase64(documentHash))).content
+    t.equal(document.documentType, "_document")
+    t.equal(document.state, "define")
+    t.deepEqual(document.editors, [alice.agentKey])
+    const sections = _.keyBy(document.content, "name")
+    t.deepEqual(Object.keys(sections), ["title", "scope", "steps"])
+    t.equal(sections["title"].content, "Bee")
+    // templates hand down their boilerplate, processes only the section to fill in
+    t.equal(sections["scope"].content, "What this covers")
+    t.equal(sections["steps"].content, "")
+    t.deepEqual(document.content.map((s: any) => s.sectionType), ["c", "c", "c"])
+    t.equal(sections["scope"].sourcePath, "a")
+    t.deepEqual(sections["scope"].sourceUnit, a.unitHash)
+    t.equal(sections["steps"].sourcePath, "soc_proto.process.define.declaration")
+    t.deepEqual(sections["steps"].sourceUnit, process.unitHash)
+    t.equal(document.meta["template:scope"], templateHash)
+    t.equal(document.meta["template:steps"], process.documentHash)
+
+    // the document is linked at the unit's path like any other
+    const current = await how(alice.cell, 'get_current_document', b.info.hash)
+    t.equal(current.content.hash, documentHash)
+  })
+})
+
+test("sections are inherited down the tree", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const a = await createUnitWithDocument(alice.cell, null, "a", [
+      makeSection("title", "A"),
+      makeSection("budget", "how much it costs", "text/plain", "r"),
+    ])
+    const b = await createUnitWithDocument(alice.cell, "a", "b", [
+      makeSection("owner", "who looks after it", "text/plain", "r"),
+      makeSection("steps", "what to do", "text/plain", "p"),
+    ])
+
+    const inherited = await how(alice.cell, 'get_sections_from_hierarchy', {path: "a.b.c", sectionType: "r"})
+    t.deepEqual(inherited.map((i: any) => [i.path, i.section.name]), [["a", "budget"], ["a.b", "owner"]])
+    t.deepEqual(inherited[0].unitHash, a.unitHash)
+    t.equal(inherited[0].documentHash, a.documentHash)
+    t.equal(inherited[1].documentHash, b.documentHash)
+    // what is inherited is a section to fill in that remembers where it came from
+    t.deepEqual(_.pick(inherited[1].section, ["content", "sectionType", "sourcePath"]), {content: "", sectionType: "c", sourcePath: "a.b"})
+    t.deepEqual(inherited[1].section.sourceUnit, b.unitHash)
+
+    // leading segments can be skipped
+    const skipped = await how(alice.cell, 'get_sections_from_hierarchy', {path: "a.b", start: 2, sectionType: "r"})
+    t.deepEqual(skipped.map((i: any) => i.section.name), ["owner"])
+
+    const processes = await how(alice.cell, 'get_sections_from_hierarchy', {path: "a.b", sectionType: "p"})
+    t.deepEqual(processes.map((i: any) => i.section.name), ["steps"])
+
+    // only the current revision of a document counts
+    await how(alice.cell, 'update_document', {hash: b.documentHash, path: "a.b", document: makeDocument(b.unitHash, [makeSection("owner", "", "text/plain", "c")])})
+    const updated = await how(alice.cell, 'get_sections_from_hierarchy', {path: "a.b", sectionType: "r"})
+    t.deepEqual(updated.map((i: any) => i.section.name), ["budget"])
+  })
+})
+
+test("collections gather the contributions of the units below", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const contribution = (name: string, content: string) => ({...makeSection(name, content), sourcePath: "a"})
+    const a = await createUnitWithDocument(alice.cell, null, "a", [
+      makeSection("title", "A"),
+      makeSection("proposal", "a proposal for the collection", "text/plain", "d"),
+    ])
+    const y = await createUnitWithDocument(alice.cell, "a", "y", [makeSection("title", "Y"), contribution("proposal", "from y")])
+    const x = await createUnitWithDocument(alice.cell, "a", "x", [makeSection("title", "X"), contribution("proposal", "from x"), makeSection("notes", "not for a")])
+    await createUnitWithDocument(alice.cell, "a.x", "z", [makeSection("title", "Z"), contribution("extra", "from z")])
+    // units without a document contribute nothing
+    await how(alice.cell, 'create_unit', {state: "define", unit: makeUnit("a", "w")})
+
+    const collection = await how(alice.cell, 'get_collection', a.unitHash)
+    t.equal(collection.path, "a")
+    t.equal(collection.documentHash, a.documentHash)
+    t.deepEqual(collection.definitions, [{name: "proposal", contentType: "text/plain", description: "a proposal for the collection"}])
+    // depth first, siblings in path order
+    t.deepEqual(collection.items.map((i: any) => [i.path, i.title, i.section.name, i.section.content]), [
+      ["a.x", "X", "proposal", "from x"],
+      ["a.x.z", "Z", "extra", "from z"],
+      ["a.y", "Y", "proposal", "from y"],
+    ])
+    t.deepEqual(collection.items[0].unitHash, x.unitHash)
+    t.equal(collection.items[2].documentHash, y.documentHash)
+    t.deepEqual(collection.conflicts, [])
+  })
+})
+
+test("documents are checked against the requirements above them", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const requirement = (name: string, def: any) => makeSection(name, JSON.stringify(def), "text/plain", "r")
+    const a = await createUnitWithDocument(alice.cell, null, "a", [
+      makeSection("title", "A"),
+      requirement("summary", {minLength: 10, filledInState: "define"}),
+      requirement("budget", {contentType: "number"}),
+      requirement("reviewer", {editors: [alice.agentKey]}),
+      makeSection("owner", "who looks after it", "text/plain", "r"),
+    ])
+    await how(alice.cell, 'create_document', {path: "a", document: makeDocument(a.unitHash, [makeSection("scope", "What this covers")], "_template")})
+    const b = await createUnitWithDocument(alice.cell, "a", "b", [
+      makeSection("summary", "too short"),
+      makeSection("budget", "12"),
+      makeSection("reviewer", "someone"),
+      makeSection("scope", "everything"),
+    ])
+
+    const report = await how(alice.cell, 'check_requirements', decodeHashFromBase64(b.documentHash))
+    t.equal(report.documentHash, b.documentHash)
+    t.equal(report.state, "define")
+    t.equal(report.passed, false)
+    const results = _.keyBy(report.results, "section")
+    t.deepEqual(Object.keys(results).sort(), ["budget", "owner", "reviewer", "scope", "summary"])
+    t.deepEqual(results["summary"].failures, [{type: "TooShort", content: {min: 10, actual: 9}}])
+    t.deepEqual(results["budget"].failures, [{type: "ContentType", content: {expected: "number", actual: "text/plain"}}])
+    t.deepEqual(results["reviewer"].failures, [{type: "NoEligibleEditor"}])
+    // free-form requirements only describe the section
+    t.deepEqual(results["owner"].failures, [{type: "Missing"}])
+    t.equal(results["owner"].requirement.description, "who looks after it")
+    // every section of a template is required
+    t.equal(results["scope"].passed, true)
+    t.equal(results["scope"].sourcePath, "a")
+
+    // empty sections only fail once the document has reached the state they must be filled in by
+    const c = await createUnitWithDocument(alice.cell, "a", "c", [makeSection("summary", "")])
+    const empty = _.keyBy((await how(alice.cell, 'check_requirements', decodeHashFromBase64(c.documentHash))).results, "section")
+    t.deepEqual(empty["summary"].failures, [{type: "NotFilledIn", content: {state: "define"}}])
+  })
+})
+
+test("section content is checked against its content type", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    await createProcess(alice.cell, [makeSection("config", JSON.stringify({type: "object", required: ["size"]}), "text/plain", "p")])
+    const a = await how(alice.cell, 'create_unit', {state: "define", unit: makeUnit(null, "a")})
+    const create = (sections: Array<any>) => how(alice.cell, 'create_document', {path: "a", document: makeDocument(a.info.hash, sections)})
+
+    await t.rejects(create([makeSection("count", "twelve", "number")]))
+    await t.rejects(create([makeSection("due", "2023-02-30", "date")]))
+    await t.rejects(create([makeSection("level", "extreme", "enum;values=low,high")]))
+    // JSON naming a schema has to follow it
+    await t.rejects(create([makeSection("settings", `{"color": "red"}`, "application/json;schema=config")]))
+    await t.rejects(create([makeSection("settings", `{"size": 3}`, "application/json;schema=nonesuch")]))
+
+    // valid content is stored in its canonical form
+    const documentHash = await create([
+      makeSection("count", " 12 ", "number"),
+      makeSection("settings", `{ "size" : 3 }`, "application/json;schema=config"),
+      makeSection("empty", "", "number"),
+    ])
+    const document = await how(alice.cell, 'get_document', decodeHashFromBase64(documentHash))
+    t.deepEqual(document.content.content.map((s: any) => s.content), ["12", `{"size":3}`, ""])
+  })
+})
+
+test("documents can follow the templates they were taken from", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const process = await createProcess(alice.cell, [makeSection("steps", "1. declare\n", "text/plain", "p")])
+    const reviseProcess = async (hash: string, steps: string) =>
+      how(alice.cell, 'update_document', {hash, path: process.path, document: makeDocument(process.unitHash, [makeSection("steps", steps, "text/plain", "p")])})
+    const a = await how(alice.cell, 'create_unit', {state: "define", unit: makeUnit(null, "a")})
+    const instantiated = await how(alice.cell, 'instantiate_template', {unitHash: a.info.hash, templatePath: "a"})
+    const b = await how(alice.cell, 'create_unit', {state: "define", unit: makeUnit(null, "b")})
+    const untouched = await how(alice.cell, 'instantiate_template', {unitHash: b.info.hash, templatePath: "b"})
+    const document = (await how(alice.cell, 'get_document', decodeHashFromBase64(instantiated))).content
+    document.content.find((s: any) => s.name == "steps").content = "we declare"
+    const filled = await how(alice.cell, 'update_document', {hash: instantiated, path: "a", document})
+    const drift = (hash: string) => how(alice.cell, 'get_template_drift', decodeHashFromBase64(hash))
+
+    const current = await drift(filled)
+    t.equal(current.length, 1)
+    t.deepEqual(_.pick(current[0], ["section", "sourcePath", "recordedRevision", "currentRevision", "status"]), {
+      section: "steps", sourcePath: process.path, recordedRevision: process.documentHash, currentRevision: process.documentHash, status: "Current",
+    })
+
+    const revised = await reviseProcess(process.documentHash, "1. declare\n2. review\n")
+    const drifted = await drift(filled)
+    t.equal(drifted[0].status, "Drifted")
+    t.equal(drifted[0].currentRevision, revised)
+    t.deepEqual(drifted[0].changes, [{op: "Equal", text: "1. declare\n"}, {op: "Insert", text: "2. review\n"}])
+
+    const upgrade = (documentHash: string, mode: string) =>
+      how(alice.cell, 'upgrade_document_template', {documentHash: decodeHashFromBase64(documentHash), sections: ["steps"], mode})
+    const steps = async (hash: string) =>
+      (await how(alice.cell, 'get_document', decodeHashFromBase64(hash))).content.content.find((s: any) => s.name == "steps")
+
+    // recording only leaves what was written and just moves the revision on
+    const upgraded = await upgrade(filled, "RecordOnly")
+    t.equal(upgraded.upgraded[0].status, "Drifted")
+    t.equal((await steps(upgraded.hash)).content, "we declare")
+    const kept = (await how(alice.cell, 'get_document', decodeHashFromBase64(upgraded.hash))).content
+    t.equal(kept.meta["template:steps"], revised)
+    t.equal((await drift(upgraded.hash))[0].status, "Current")
+    await t.rejects(how(alice.cell, 'upgrade_document_template', {documentHash: decodeHashFromBase64(upgraded.hash), sections: ["title"]}))
+
+    // merging is the default, a section nobody wrote in yet takes the new template text
+    const merged = await how(alice.cell, 'upgrade_document_template', {documentHash: decodeHashFromBase64(untouched), sections: ["steps"]})
+    t.deepEqual(merged.conflicts, [])
+    t.equal((await steps(merged.hash)).content, "1. declare\n2. review\n")
+
+    // where both the authors and the template changed the section, both are kept
+    const again = await reviseProcess(revised, "1. declare\n2. review\n3. publish\n")
+    const conflicted = await upgrade(upgraded.hash, "Merge")
+    t.deepEqual(conflicted.conflicts, ["steps"])
+    t.equal((await steps(conflicted.hash)).content, "<<<<<<< left\nwe declare\n=======\n1. declare\n2. review\n3. publish\n\n>>>>>>> right")
+
+    // replacing starts the section over from the template text
+    const replaced = await upgrade(conflicted.hash, "Replace")
+    t.deepEqual(_.pick(await steps(replaced.hash), ["content", "sectionType", "sourcePath"]), {
+      content: "1. declare\n2. review\n3. publish\n", sectionType: "c", sourcePath: process.path,
+    })
+    const fresh = (await how(alice.cell, 'get_document', decodeHashFromBase64(replaced.hash))).content
+    t.equal(fresh.meta["template:steps"], again)
+
+    // documents from before revisions were recorded can't say how far they drifted
+    const u = await how(alice.cell, 'create_unit', {state: "define", unit: makeUnit(null, "u")})
+    const old = await how(alice.cell, 'create_document', {path: "u", document: makeDocument(u.info.hash, [
+      {...makeSection("steps", "old steps"), sourcePath: process.path, sourceUnit: process.unitHash},
+    ])})
+    const unknown = await drift(old)
+    t.deepEqual(_.pick(unknown[0], ["status", "recordedRevision", "currentRevision", "changes"]), {status: "Unknown", recordedRevision: null, currentRevision: again, changes: []})
+  })
+})
+
+test("markdown files are imported into the sections of a document", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    await createUnitWithDocument(alice.cell, null, "a", [
+      makeSection("title", "the title", "text/plain", "r"),
+      makeSection("summary", "what it is about", "text/plain", "r"),
+      makeSection("budget", "how much it costs", "number", "r"),
+    ])
+    const b = await how(alice.cell, 'create_unit', {state: "define", unit: makeUnit("a", "b")})
+    const markdown = "# Bee Plan\n\nwhy bees\n\n## Summary\n\nAll about *bees*\n\n## Budget\nquite a lot\n\n## Risks\nstings\n"
+
+    const report = await how(alice.cell, 'import_markdown', {path: "a.b", markdown})
+    t.equal(report.created, true)
+    t.deepEqual(report.matched, ["title", "summary"])
+    t.equal(report.preambleIgnored, true)
+    // prose doesn't fit a number section, and nothing is called risks
+    t.deepEqual(report.unmatched, [
+      {heading: "Budget", line: 10, added: false, incompatible: "quite a lot is not a number"},
+      {heading: "Risks", line: 13, added: false, incompatible: null},
+    ])
+    const current = await how(alice.cell, 'get_current_document', b.info.hash)
+    t.equal(current.content.hash, report.documentHash)
+    const sections = _.keyBy(current.content.content.content, "name")
+    t.equal(sections["title"].content, "Bee Plan")
+    t.deepEqual(_.pick(sections["summary"], ["content", "contentType"]), {content: "All about *bees*", contentType: "text/markdown"})
+    t.equal(sections["budget"].content, "")
+
+    // importing again updates the document, optionally adding what matched nothing
+    const again = await how(alice.cell, 'import_markdown', {path: "a.b", markdown: "## risks\nstings\n\n## BUDGET\n 12 \n", options: {addUnmatched: true}})
+    t.equal(again.created, false)
+    t.deepEqual(again.matched, ["budget"])
+    t.deepEqual(again.unmatched, [{heading: "risks", line: 1, added: true, incompatible: null}])
+    const updated = _.keyBy((await how(alice.cell, 'get_current_document', b.info.hash)).content.content.content, "name")
+    t.deepEqual(_.pick(updated["budget"], ["content", "contentType"]), {content: "12", contentType: "number"})
+    t.deepEqual(_.pick(updated["risks"], ["content", "contentType", "sourcePath"]), {content: "stings", contentType: "text/markdown", sourcePath: "_manual"})
+    t.equal(updated["summary"].content, "All about *bees*")
+  })
+})
+
+test("documents are rendered for publishing", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const unit = {...makeUnit(null, "a", [alice.agentKey]), shortName: "Unit A"}
+    const output = await how(alice.cell, 'create_unit', {state: "define", unit})
+    const documentHash = await how(alice.cell, 'create_document', {path: "a", document: makeDocument(output.info.hash, [
+      makeSection("title", "A"),
+      makeSection("summary", "Hello <b>world</b> [x](javascript:y)", "text/markdown"),
+      makeSection("budget", "how much it costs", "number", "r"),
+      makeSection("data", "{\"a\": 1}", "application/json"),
+    ])})
+    const hash = decodeHashFromBase64(documentHash)
+    const comment = await how(alice.cell, 'create_comment', {document: hash, section: "summary", start: 0, end: 5, text: "nice", suggestion: null})
+    await how(alice.cell, 'mark_document', [{hash: comment, markType: 1, mark: "approved"}])
+    const onData = await how(alice.cell, 'create_comment', {document: hash, section: "data", start: 0, end: 1, text: "why a?", suggestion: null})
+    await how(alice.cell, 'mark_document', [{hash: onData, markType: 1, mark: "approved"}])
+
+    const markdown = await how(alice.cell, 'render_document', {hash, format: "Markdown"})
+    t.equal(markdown, [
+      "# Unit A", "",
+      "- **Path:** `a`",
+      "- **Version:** vidx:1",
+      "- **State:** define",
+      `- **Stewards:** \`${alice.agentKey}\``, "",
+      "## title", "", "A", "",
+      "## summary", "", "Hello <b>world</b> [x](javascript:y)", "",
+      "## data", "", "```json", "{\"a\":1}", "```", "",
+    ].join("\n"))
+
+    // markers would break structured content, so they follow such sections instead
+    const annotated = await how(alice.cell, 'render_document', {hash, format: "Markdown", includeComments: true})
+    t.ok(annotated.includes("## data\n\n```json\n{\"a\":1}\n```\n\n[^2]\n"))
+    t.ok(annotated.includes("[^2]: (data) why a?"))
+
+    // html is sanitized, and approved comments become footnotes when asked for
+    const html = await how(alice.cell, 'render_document', {hash, format: "Html", includeComments: true})
+    t.ok(html.includes("<h1>Unit A</h1>"))
+    t.ok(html.includes("<h2>summary</h2>\n<p>Hello<sup id=\"fnref-1\"><a href=\"#fn-1\">1</a></sup> &lt;b&gt;world&lt;/b&gt; x</p>"))
+    t.ok(html.includes("<li id=\"fn-1\">(summary) nice</li>"))
+    t.ok(html.includes("<pre><code>{&quot;a&quot;:1}</code></pre>\n<p><sup id=\"fnref-2\"><a href=\"#fn-2\">2</a></sup></p>\n</section>"))
+    t.notOk(html.includes("javascript"))
+    t.notOk(html.includes("budget"))
+  })
+})
+
+test("exported trees are imported with their provenance", async (t) => {
+  let bundle: any
+  let aliceKey: string
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    aliceKey = alice.agentKey
+    const a = await createUnitWithDocument(alice.cell, null, "a", [makeSection("title", "A"), makeSection("summary", "Hello world")], [alice.agentKey])
+    const revised = await how(alice.cell, 'update_document', {hash: a.documentHash, path: "a", document: makeDocument(a.unitHash, [makeSection("title", "A"), makeSection("summary", "Hello there world")])})
+    await how(alice.cell, 'mark_document', [{hash: revised, markType: 3, mark: "approved"}])
+    const comment = await how(alice.cell, 'create_comment', {document: decodeHashFromBase64(revised), section: "summary", start: 0, end: 5, text: "", suggestion: "Howdy"})
+    await how(alice.cell, 'mark_document', [{hash: comment, markType: 1, mark: "approved"}])
+    await createUnitWithDocument(alice.cell, "a", "b", [makeSection("title", "B")])
+
+    bundle = await how(alice.cell, 'export_tree', "")
+    t.deepEqual(bundle.units.map((u: any) => u.unit.pathAbbreviation).sort(), ["a", "b"])
+    const exported = bundle.documents.find((d: any) => d.hash == revised)
+    t.deepEqual(exported.previous, [a.documentHash])
+    t.deepEqual(exported.paths, ["a"])
+    t.deepEqual(_.pick(exported.marks[0], ["markType", "mark", "author"]), {markType: 3, mark: "approved", author: alice.agentKey})
+    t.equal(bundle.commentLinks.length, 1)
+  })
+
+  await runScenario(async (scenario: Scenario) => {
+    const [bobbo] = await setupPlayers(scenario)
+    const report = await how(bobbo.cell, 'initialize', {bundle})
+    t.equal(report.createdUnits.length, 2)
+    t.equal(report.createdDocuments.length, bundle.documents.length)
+    t.equal(report.commentLinks, 1)
+    t.deepEqual(report.skipped, [])
+
+    // entries keep their hashes, and so everything that refers to them
+    const unitA = bundle.units.find((u: any) => u.unit.pathAbbreviation == "a")
+    const revised = bundle.documents.find((d: any) => d.previous.length > 0)
+    const current = await how(bobbo.cell, 'get_current_document', decodeHashFromBase64(unitA.hash))
+    t.equal(current.content.hash, revised.hash)
+
+    // the original author and time are kept as provenance
+    t.deepEqual(await how(bobbo.cell, 'get_provenance', decodeHashFromBase64(unitA.hash)), {author: aliceKey, timestamp: unitA.timestamp})
+    t.deepEqual(await how(bobbo.cell, 'get_provenance', decodeHashFromBase64(revised.hash)), {author: aliceKey, timestamp: revised.timestamp})
+
+    // marks too, so exporting again keeps where they came from
+    const reexported = await how(bobbo.cell, 'export_tree', "")
+    t.deepEqual(reexported.documents.find((d: any) => d.hash == revised.hash).marks, revised.marks)
+    // but bobbo made the imported approval, and bobbo isn't a steward, so it doesn't count
+    const applied = await how(bobbo.cell, 'apply_approved_comments', decodeHashFromBase64(revised.hash))
+    t.equal(applied.hash, null)
+    t.deepEqual(applied.skipped.map((s: any) => s.reason), ["not approved by a steward"])
+    // what the importing agent writes itself has no provenance
+    const own = await how(bobbo.cell, 'update_document', {hash: revised.hash, path: "a", document: {...revised.document, content: [makeSection("title", "A"), makeSection("summary", "Howdy there world")]}})
+    t.equal(await how(bobbo.cell, 'get_provenance', decodeHashFromBase64(own)), null)
+
+    // importing again finds everything in place
+    const again = await how(bobbo.cell, 'initialize', {bundle})
+    t.equal(again.createdUnits.length + again.createdDocuments.length + again.commentLinks, 0)
+    t.deepEqual(_.uniq(again.skipped.map((s: any) => s.reason.type)).sort(), ["EntryExists", "UnitExists"])
+  })
+})
+
+test("initialize can be run again and reports what it did", async (t) => {
+  await runScenario(async (scenario: Scenario) => {
+    const [alice] = await setupPlayers(scenario)
+    const initializer = (path: string, content: Array<any>) => ({path, documentType: "_document", editors: [], content})
+    const init = {
+      units: [["define", makeUnit(null, "a")], ["define", makeUnit("a", "b")]],
+      documents: [initializer("a", [makeSection("title", "A")])],
+    }
+    const report = await how(alice.cell, 'initialize', init)
+    t.deepEqual(report.createdUnits.map((i: any) => i.path), ["a", "a.b"])
+    t.deepEqual(report.createdDocuments.map((i: any) => i.path), ["a"])
+    t.deepEqual(report.skipped, [])
+    const [aItem, bItem] = report.createdUnits
+
+    const again = await how(alice.cell, 'initialize', init)
+    t.deepEqual(again.createdUnits, [])
+    t.deepEqual(again.createdDocuments, [])
+    t.deepEqual(again.skipped, [
+      {kind: "unit", path: "a", hash: aItem.hash, reason: {type: "UnitExists"}},
+      {kind: "unit", path: "a.b", hash: bItem.hash, reason: {type: "UnitExists"}},
+      {kind: "document", path: "a", hash: report.createdDocuments[0].hash, reason: {type: "DocumentExists"}},
+    ])
+
+    // a different unit at a path keeps the one that is there
+    const other = await how(alice.cell, 'initialize', {units: [["define", {...makeUnit(null, "a"), shortName: "Other"}]], documents: []})
+    t.deepEqual(other.createdUnits, [])
+    t.deepEqual(other.skipped[0].reason, {type: "OtherUnitAtPath", content: aItem.hash})
+
+    // the whole payload is checked before anything is written
+    const process = [
+      ["define", makeUnit(null, "soc_proto")],
+      ["define", makeUnit("soc_proto", "process")],
+      ["define", makeUnit("soc_proto.process", "define")],
+      ["define", makeUnit("soc_proto.process.define", "declaration")],
+      ["define", makeUnit(null, "s")],
+    ]
+    const withSettings = (settings: string) => ({units: process, documents: [
+      initializer("soc_proto.process.define.declaration", [makeSection("config", JSON.stringify({type: "object", required: ["size"]}), "text/plain", "p")]),
+      initializer("s", [makeSection("settings", settings, "application/json;schema=config")]),
+    ]})
+    await t.rejects(how(alice.cell, 'initialize', {units: [["define", makeUnit("nowhere", "c")]], documents: []}))
+    await t.rejects(how(alice.cell, 'initialize', {units: [], documents: [initializer("nowhere", [])]}))
+    // schemas in the payload apply to the payload's documents
+    await t.rejects(how(alice.cell, 'initialize', withSettings(`{"color": "red"}`)))
+
+    const valid = await how(alice.cell, 'initialize', withSettings(`{ "size" : 3 }`))
+    t.equal(valid.createdUnits.length, 5)
+    t.equal(valid.createdDocuments.length, 2)
+    const settings = await how(alice.cell, 'get_document', decodeHashFromBase64(valid.createdDocuments[1].hash))
+    t.equal(settings.content.content[0].content, `{"size":3}`)
+  })
+})
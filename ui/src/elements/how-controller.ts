@@ -4,12 +4,10 @@ import { state, property, query } from "lit/decorators.js";
 import { get } from "svelte/store";
 
 import { sharedStyles } from "../sharedStyles";
-import {howContext, Unit, Dictionary, Initialization, DocumentOutput, Document, DocType, Node, Section, DocumentInitializer, UnitFlags} from "../types";
+import {howContext, Unit, Dictionary, Initialization, DocumentOutput, Document, DocType, Node, Section, DocumentInitializer, UnitFlags, InitializationReport} from "../types";
 import { HowStore } from "../how.store";
 import { HowUnit } from "./how-unit";
 import { HowTree } from "./how-tree";
-import { initialTreeHolochain } from "../initHolochain";
-import { initialTreeSimple } from "../initSimple";
 import { HowUnitDialog } from "./how-unit-dialog";
 import { HowUnitDialogEdit } from "./how-unit-dialog-edit";
 import { ScopedElementsMixin } from "@open-wc/scoped-elements";
@@ -372,28 +370,29 @@ export class HowController extends ScopedElementsMixin(LitElement) {
     alert(`exported as: ${fileName}`)
   }
   async doInitializeDHT(init:Initialization) {
+    await this.initializeOnce(() => this._store.initilize(init))
+  }
+
+  async initializeOnce(run: () => Promise<InitializationReport>) {
     if (this.initializing || this.initialized) {
       console.log("initialization allready started")
       return;
     }
     console.log("starting initialization")
     this.initializing = true  // because checkInit gets call whenever profiles changes...
-    await this._store.initilize(init);
+    await run();
     this.initializing = false
     console.log("initialization complete")
     this.checkInit()
   }
 
+  // the initial trees are built by the zome, see presets.rs
   async addInitialHolochain() {
-    const init  = initialTreeHolochain(this._store.myAgentPubKey)
-
-    await this.doInitializeDHT(init)
+    await this.initializeOnce(() => this._store.initializeWithPreset("holochain"))
   }
 
   async addInitialSimple() {
-    const init  = initialTreeSimple(this._store.myAgentPubKey)
-
-    await this.doInitializeDHT(init)
+    await this.initializeOnce(() => this._store.initializeWithPreset("simple"))
   }
 
   async checkInit() {
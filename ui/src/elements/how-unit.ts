@@ -130,7 +130,7 @@ export class HowUnit extends ScopedElementsMixin(LitElement) {
 
   private async handleConfirmCollect(version:string, def:Section) {
     const path = this.getPath()
-    const sections = await this._store.getCollectionSections(path)
+    const sections = await this._store.getCollectionSections(this.currentUnitEh)
     sections.unshift({
       name: "title",
       sourcePath: path,
@@ -1,5 +1,5 @@
 import { AppAgentClient, EntryHashB64, AgentPubKeyB64, AppAgentCallZomeRequest, RoleName, encodeHashToBase64, decodeHashFromBase64, EntryHash } from '@holochain/client';
-import { UnitInput, RustNode, RustTree, Initialization, DocumentOutput, DocumentInput, UpdateDocumentInput, AdvanceStateInput, UnitOutput, MarkDocumentInput, HowSignal, Unit, UpdateUnitInput} from './types';
+import { UnitInput, RustNode, RustTree, Initialization, InitializationReport, TreeBundle, DocumentOutput, DocumentsOutput, DocumentInput, InstantiateTemplateInput, SectionsFromHierarchyInput, InheritedSection, Collection, RequirementsReport, TemplateDrift, ImportOptions, ImportReport, UpgradeMode, UpgradeTemplateOutput, UpdateDocumentInput, AdvanceStateInput, UnitOutput, MarkDocumentInput, HowSignal, Unit, UpdateUnitInput} from './types';
 import { ActionHash  } from '@holochain/client';
 
 export class HowService {
@@ -13,10 +13,22 @@ export class HowService {
     return encodeHashToBase64(this.client.myPubKey);
   }
 
-  async initialize(input: Initialization): Promise<EntryHashB64> {
+  async initialize(input: Initialization): Promise<InitializationReport> {
     return this.callZome('initialize', input);
   }
 
+  async initializeWithPreset(name: string): Promise<InitializationReport> {
+    return this.callZome('initialize_with_preset', name);
+  }
+
+  async listPresets(): Promise<Array<string>> {
+    return this.callZome('list_presets', null);
+  }
+
+  async exportTree(root: string = ""): Promise<TreeBundle> {
+    return this.callZome('export_tree', root);
+  }
+
   async createUnit(unit: UnitInput): Promise<UnitOutput> {
     return this.callZome('create_unit', unit);
   }
@@ -43,6 +55,38 @@ export class HowService {
     return this.callZome('create_document', input);
   }
 
+  async getSectionsFromHierarchy(input: SectionsFromHierarchyInput): Promise<Array<InheritedSection>> {
+    return this.callZome('get_sections_from_hierarchy', input);
+  }
+
+  async checkRequirements(input: EntryHash): Promise<RequirementsReport> {
+    return this.callZome('check_requirements', input);
+  }
+
+  async getTemplateDrift(input: EntryHash): Promise<Array<TemplateDrift>> {
+    return this.callZome('get_template_drift', input);
+  }
+
+  async upgradeDocumentTemplate(documentHash: EntryHash, sections: Array<string>, mode: UpgradeMode = "Merge"): Promise<UpgradeTemplateOutput> {
+    return this.callZome('upgrade_document_template', {documentHash, sections, mode});
+  }
+
+  async importMarkdown(path: string, markdown: string, options: ImportOptions = {}): Promise<ImportReport> {
+    return this.callZome('import_markdown', {path, markdown, options});
+  }
+
+  async renderDocument(hash: EntryHash, format: "Markdown" | "Html", includeComments: boolean = false): Promise<string> {
+    return this.callZome('render_document', {hash, format, includeComments});
+  }
+
+  async getCollection(input: EntryHash): Promise<Collection> {
+    return this.callZome('get_collection', input);
+  }
+
+  async instantiateTemplate(input: InstantiateTemplateInput): Promise<EntryHashB64> {
+    return this.callZome('instantiate_template', input);
+  }
+
   async updateDocument(input: UpdateDocumentInput): Promise<EntryHashB64> {
     return this.callZome('update_document', input);
   }
@@ -55,12 +99,17 @@ export class HowService {
     return this.callZome('mark_document', input);
   }
 
+  async getDocumentHeads(input: EntryHash): Promise<Array<EntryHashB64>> {
+    return this.callZome('get_document_heads', input);
+  }
+
   async advanceState(input: AdvanceStateInput): Promise<EntryHashB64> {
     return this.callZome('advance_state', input);
   }
 
   async getDocuments(path: string): Promise<Array<DocumentOutput>> {
-    return this.callZome('get_documents', path);
+    const output: DocumentsOutput = await this.callZome('get_documents', path);
+    return output.documents
   }
 
   async getTree(): Promise<Array<RustNode>> {
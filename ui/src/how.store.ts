@@ -9,6 +9,7 @@ import {
   RustNode,
   Node,
   Initialization,
+  InitializationReport,
   Document,
   DocumentOutput,
   Process,
@@ -101,59 +102,19 @@ export class HowStore {
   // get all of the sections needed for a specific process by getting the template contents
   // for that proccess hierarchy
   async getSectionsFromHierarcy(path: string, start: number, sectionType: SectionType): Promise<Array<Section>> {
-    path = `.${path}`
-    let sections: Array<Section> = []
-    let segments = path.split(".")
-
-    let i = start+1
-    while (i <= segments.length) {
-      const walk = segments.slice(0,i).join(".")
-      i += 1
-      // find the sections of the given type at this level and add them into the sections
-      await this.pullDocuments(walk)
-      let docs = get(this.documentPaths)[walk]
-      if (docs) {
-        docs = docs.filter(doc => doc.updatedBy.length==0 && doc.deletedBy.length==0)
-        for (const doc of docs) {
-          if (doc.content.documentType == DocType.Document) {
-            let newSections = doc.content.getSectionsByType(sectionType).map(s => cloneDeep(s))
-            for (const section of newSections) {
-              section.content = ""
-              section.sectionType = SectionType.Content
-              section.sourcePath = walk.slice(1)  // drop leading .
-              section.sourceUnit = doc.content.unitHash
-            }
-            sections = sections.concat(newSections)
-          } 
-        }
-      }
-    }
-    return sections
-  }
-
-  async getCollectionSections(path: string): Promise<Array<Section>> {
-    let sections: Array<Section> = []
-
-    const branchPaths = this.getBranchPaths(path)
-    for (const p of branchPaths) {
-      await this.pullDocuments(p)
-      let docs = get(this.documentPaths)[p]
-      docs = docs.filter(doc => doc.updatedBy.length==0 && doc.deletedBy.length==0)
-      for (const doc of docs) {
-        if (doc.content.documentType == DocType.Document) {
-          let title = doc.content.getSection("title")
-          let secs = doc.content.getSectionsBySource(path).map(s => cloneDeep(s))
-          // rewrite the section to say where it came from
-          for (const s of secs) {
-            s.sourcePath = p
-            s.name = title.content
-            sections.push(s)
-          }
-        }
-      }
-    }
-    console.log("COLLECTION SECTIONS", sections)
-    return sections
+    const inherited = await this.service.getSectionsFromHierarchy({path, start, sectionType})
+    return inherited.map(i => i.section)
+  }
+
+  async getCollectionSections(unitEh: EntryHashB64): Promise<Array<Section>> {
+    const collection = await this.service.getCollection(decodeHashFromBase64(unitEh))
+    // rewrite the sections to say where they came from
+    return collection.items.map(item => {
+      const s = cloneDeep(item.section)
+      s.sourcePath = item.path
+      s.name = item.title
+      return s
+    })
   }
 
 
@@ -292,7 +253,7 @@ export class HowStore {
     const path = this.getDocumentPath(hash)
     let newHash: EntryHashB64 = ""
     if (path) {
-      newHash = await this.service.updateDocument({hash, document, path})
+      newHash = await this.service.updateDocument({hash, document, path, expectedHead: hash})
       this.markDocumentUpdated(path, hash, newHash)
       this.pullDocuments(path)
     }
@@ -329,9 +290,7 @@ export class HowStore {
       if (documentOutput) {
         let doc = cloneDeep(documentOutput.content)
         doc.state = state
-        const processPath = unit.processPathForState(state)
-        doc.appendSections(await this.getSectionsFromHierarcy(processPath, 2, SectionType.Process))
-  
+        // the process sections for the new state are added by advance_state
         const newDocumentHash = await this.service.advanceState({
             newState: state,
             unitHash: decodeHashFromBase64(unitHash),
@@ -414,23 +373,9 @@ export class HowStore {
 
   async initializeUnit(unitEh: EntryHashB64, state: string) : Promise<void>  {
     const unit = this.unit(unitEh)
-    const doc = new Document({unitHash: decodeHashFromBase64(unitEh), documentType: DocType.Document})
-    // TODO 
-    doc.editors = unit.stewards
-    doc.state = state
-
-    doc.appendSections(await this.getSectionsFromHierarcy(unit.parents[0], 0, SectionType.Requirement))
-    const proc = unit.processes[0]
-    if (proc) {
-      const processPath = `${proc[0]}.${proc[1]}`
-      await this.pullDocuments(processPath)
-      doc.appendSections(await this.getSectionsFromHierarcy(processPath, 2, SectionType.Process))
-    }
-
-    doc.setSection("title", unit.shortName)
-    console.log("ADDING DOC", doc)
+    await this.service.instantiateTemplate({unitHash: decodeHashFromBase64(unitEh), templatePath: unit.parents[0]})
     const path = `${unit.parents[0]}.${unit.pathAbbreviation}`
-    await this.addDocument(path, doc)
+    await this.pullDocuments(path)
   }
 
   async addUnit(unit: Unit, state: string) : Promise<EntryHashB64> {
@@ -448,8 +393,12 @@ export class HowStore {
     return encodeHashToBase64(unitOutput.info.hash)
   }
 
-  async initilize(input: Initialization) : Promise<void> {
-    await this.service.initialize(input)
+  async initilize(input: Initialization) : Promise<InitializationReport> {
+    return await this.service.initialize(input)
+  }
+
+  async initializeWithPreset(name: string) : Promise<InitializationReport> {
+    return await this.service.initializeWithPreset(name)
   }
 
   unit(unitEh: EntryHashB64): Unit {
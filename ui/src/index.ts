@@ -11,5 +11,3 @@ export * from './elements/svg-button';
 export * from './how.service';
 export * from './how.store';
 export * from './types';
-export * from './initHolochain';
-export * from './initSimple';
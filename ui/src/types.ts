@@ -12,6 +12,41 @@ export type Dictionary<T> = { [key: string]: T };
 export interface Initialization {
   units: Array<[string,Unit]>,
   documents: Array<DocumentInitializer>,
+  bundle?: TreeBundle,
+}
+
+export interface InitializedItem {
+  path: string,
+  hash: EntryHashB64,
+}
+
+export type SkipReason =
+  | { type: "UnitExists" }
+  | { type: "OtherUnitAtPath", content: EntryHashB64 }
+  | { type: "DocumentExists" }
+  | { type: "EntryExists" }
+
+export interface SkippedItem {
+  kind: "unit" | "document" | "commentLink",
+  path: string,
+  hash?: EntryHashB64,
+  reason: SkipReason,
+}
+
+export interface InitializationReport {
+  createdUnits: Array<InitializedItem>,
+  createdDocuments: Array<InitializedItem>,
+  commentLinks: number,
+  skipped: Array<SkippedItem>,
+}
+
+// a tree as exported by export_tree, opaque to the UI which only passes it back to initialize
+export interface TreeBundle {
+  version: number,
+  root: string,
+  units: Array<any>,
+  documents: Array<any>,
+  commentLinks: Array<any>,
 }
 
 export type ProcessName = string
@@ -262,16 +297,97 @@ export interface DocumentOutput {
   marks: Array<Mark>,
 }
 
+export interface DocumentsOutput {
+  path: string,
+  redirectedFrom: string | undefined,
+  documents: Array<DocumentOutput>,
+}
+
 export interface DocInfo {
   hash: EntryHashB64,
   content: Document,
   updated: Timestamp,
 }
 
+export interface SectionsFromHierarchyInput {
+  path: string,
+  sectionType: SectionType,
+  start: number,
+}
+
+export interface InheritedSection {
+  path: string,
+  unitHash: EntryHash,
+  documentHash: EntryHashB64,
+  section: Section,
+}
+
+export interface CollectionItem {
+  path: string,
+  unitHash: EntryHash,
+  documentHash: EntryHashB64,
+  title: string,
+  section: Section,
+}
+
+export interface CollectionDefinition {
+  name: string,
+  contentType: string,
+  description: string,
+}
+
+export interface Collection {
+  unitHash: EntryHash,
+  path: string,
+  documentHash: EntryHashB64,
+  definitions: Array<CollectionDefinition>,
+  items: Array<CollectionItem>, // every section below the unit sourced from its path
+  conflicts: Array<string>,
+}
+
+export interface TemplateDrift {
+  section: string,
+  sourcePath: string,
+  sourceUnit: EntryHash,
+  recordedRevision?: EntryHashB64,
+  currentRevision?: EntryHashB64,
+  status: "Current" | "Drifted" | "Unknown", // unknown for documents from before template revisions were recorded
+  changes: Array<{op: "Equal" | "Insert" | "Delete", text: string}>,
+}
+
+// merge brings the template's changes into what the authors wrote, replace starts over
+// from the template text and record only leaves the changes to the authors
+export type UpgradeMode = "Merge" | "Replace" | "RecordOnly"
+
+export interface UpgradeTemplateOutput {
+  hash: EntryHashB64,
+  upgraded: Array<TemplateDrift>,
+  conflicts: Array<string>, // sections both the authors and the template changed
+}
+
+export interface ImportOptions {
+  level?: number,
+  addUnmatched?: boolean,
+}
+
+export interface ImportReport {
+  documentHash: EntryHashB64,
+  created: boolean,
+  matched: Array<string>,
+  unmatched: Array<{heading: string, line: number, added: boolean, incompatible: string | null}>,
+  preambleIgnored: boolean,
+}
+
+export interface InstantiateTemplateInput {
+  unitHash: EntryHash,
+  templatePath: string,
+}
+
 export interface UpdateDocumentInput {
   hash: EntryHashB64,
   path: string,
   document: Document,
+  expectedHead?: EntryHashB64,
 }
 
 export interface AdvanceStateInput {
@@ -348,12 +464,44 @@ export type Progress = {
 
 export type RequirementInfo = {
   description: string,
+  contentType?: string,
+  minLength?: number,
+  maxLength?: number,
+  filledInState?: string,
+  editors?: Array<AgentPubKeyB64>,
 }
 
 export const parseRequirementInfo =  (section: Section) : RequirementInfo => {
-  return JSON.parse(section.content)
+  try {
+    return JSON.parse(section.content)
+  } catch (e) {
+    return {description: section.content}
+  }
 } 
 
+export type RequirementFailure =
+  | {type: "Missing"}
+  | {type: "ContentType", content: {expected: string, actual: string}}
+  | {type: "TooShort", content: {min: number, actual: number}}
+  | {type: "TooLong", content: {max: number, actual: number}}
+  | {type: "NotFilledIn", content: {state: string}}
+  | {type: "NoEligibleEditor"}
+
+export interface RequirementResult {
+  section: string,
+  requirement: RequirementInfo,
+  sourcePath: string,
+  passed: boolean,
+  failures: Array<RequirementFailure>,
+}
+
+export interface RequirementsReport {
+  documentHash: EntryHashB64,
+  state: string,
+  passed: boolean,
+  results: Array<RequirementResult>,
+}
+
 export const parseAgentArray =  (section: Section) : Array<AgentPubKeyB64> => {
   try {
     return JSON.parse(section.content)